@@ -0,0 +1,112 @@
+//! ACPI.
+use alloc::{string::String, string::ToString, vec::Vec};
+use serde::{Deserialize, Serialize};
+
+use crate::generic::{Command, QmpCommand};
+
+/// ACPI slot type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum AcpiSlotType {
+    /// Memory slot.
+    Dimm,
+    /// Logical CPU slot.
+    Cpu,
+}
+
+/// OSPM Status Indication for a device.
+///
+/// For description of possible values of `source` and `status` fields
+/// see "_OST (OSPM Status Indication)" chapter of ACPI5.0 spec.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct AcpiOstInfo {
+    /// Device ID associated with slot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// Slot ID, unique per slot of a given `slot_type`.
+    pub slot: String,
+    /// Type of the slot.
+    pub slot_type: AcpiSlotType,
+    /// An integer containing the source event.
+    pub source: i64,
+    /// An integer containing the status code.
+    pub status: i64,
+}
+
+/// Emitted when guest executes ACPI _OST method.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct AcpiDeviceOstEvent {
+    /// OSPM Status Indication.
+    pub info: AcpiOstInfo,
+}
+
+/// Marker of 'query-acpi-ospm-status'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryAcpiOspmStatus;
+
+impl QmpCommand for QueryAcpiOspmStatus {
+    type Return = Vec<AcpiOstInfo>;
+    const NAME: &'static str = "query-acpi-ospm-status";
+}
+
+/// Return a list of `AcpiOstInfo` for devices that support status reporting via ACPI _OST method.
+#[inline]
+pub fn query_acpi_ospm_status() -> Command<(), ()> {
+    Command {
+        execute: "query-acpi-ospm-status".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AcpiDeviceOstEvent, AcpiOstInfo, AcpiSlotType, query_acpi_ospm_status};
+    use alloc::{string::ToString, vec, vec::Vec};
+
+    #[test]
+    fn query_acpi_ospm_status_result() {
+        let cmd = query_acpi_ospm_status();
+        let compact_string = r#"{"execute":"query-acpi-ospm-status"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[
+            {"device": "d1", "slot": "0", "slot-type": "DIMM", "source": 1, "status": 0},
+            {"slot": "3", "slot-type": "DIMM", "source": 0, "status": 0}
+        ]"#;
+        let value = vec![
+            AcpiOstInfo {
+                device: Some("d1".to_string()),
+                slot: "0".to_string(),
+                slot_type: AcpiSlotType::Dimm,
+                source: 1,
+                status: 0,
+            },
+            AcpiOstInfo {
+                device: None,
+                slot: "3".to_string(),
+                slot_type: AcpiSlotType::Dimm,
+                source: 0,
+                status: 0,
+            },
+        ];
+        assert_eq!(value, serde_json::from_str::<Vec<_>>(string).unwrap());
+    }
+
+    #[test]
+    fn struct_acpi_device_ost_event() {
+        let string = r#"{"info": {"device": "d1", "slot": "0", "slot-type": "CPU", "source": 1, "status": 0}}"#;
+        let value = AcpiDeviceOstEvent {
+            info: AcpiOstInfo {
+                device: Some("d1".to_string()),
+                slot: "0".to_string(),
+                slot_type: AcpiSlotType::Cpu,
+                source: 1,
+                status: 0,
+            },
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+}
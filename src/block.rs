@@ -0,0 +1,1399 @@
+//! Block devices.
+use alloc::{boxed::Box, string::String, string::ToString, vec::Vec};
+#[cfg(feature = "json")]
+use core::fmt;
+use serde::{Deserialize, Deserializer, Serialize};
+#[cfg(feature = "json")]
+use serde_json::Value;
+
+use crate::generic::{Command, Empty, QmpCommand};
+use crate::job::{JobStatus, JobType};
+
+/// Cache mode information for a block device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct BlockdevCacheInfo {
+    /// True if writeback mode is enabled.
+    pub writeback: bool,
+    /// True if the host page cache is bypassed (O_DIRECT).
+    pub direct: bool,
+    /// True if flush requests are ignored for the device.
+    pub no_flush: bool,
+}
+
+/// Describes the operation mode for the automatic conversion of plain
+/// zero writes by the OS to driver specific optimized zero write commands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BlockdevDetectZeroesOptions {
+    /// Disabled (default).
+    Off,
+    /// Enabled.
+    On,
+    /// Enabled and even try to unmap blocks if possible.
+    Unmap,
+}
+
+/// Block dirty bitmap information.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct BlockDirtyInfo {
+    /// The name of the dirty bitmap.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Number of dirty bytes according to the dirty bitmap.
+    pub count: u64,
+    /// Granularity of the dirty bitmap in bytes.
+    pub granularity: u32,
+    /// True if the bitmap is recording new writes from the guest.
+    pub recording: bool,
+    /// True if the bitmap is in-use by some operation and can not be modified.
+    pub busy: bool,
+    /// True if the bitmap was stored on disk.
+    pub persistent: bool,
+    /// True if this is a persistent bitmap that was improperly stored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inconsistent: Option<bool>,
+}
+
+/// Information about an internal snapshot.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotInfo {
+    /// Unique snapshot id.
+    pub id: String,
+    /// User chosen name.
+    pub name: String,
+    /// Size of the VM state.
+    pub vm_state_size: u64,
+    /// UTC date of the snapshot in seconds.
+    pub date_sec: i64,
+    /// Fractional part in nano seconds to be used with `date_sec`.
+    pub date_nsec: i64,
+    /// VM clock relative to boot in seconds.
+    pub vm_clock_sec: i64,
+    /// Fractional part in nano seconds to be used with `vm_clock_sec`.
+    pub vm_clock_nsec: i64,
+    /// Current instruction count if record/replay is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icount: Option<u64>,
+}
+
+/// Information about a QEMU image file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct ImageInfo {
+    /// Name of the image file.
+    pub filename: String,
+    /// Format of the image file.
+    pub format: String,
+    /// True if image is not cleanly closed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dirty_flag: Option<bool>,
+    /// Actual size on disk in bytes of the image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_size: Option<u64>,
+    /// Maximum capacity in bytes of the image.
+    pub virtual_size: u64,
+    /// Size of a cluster in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cluster_size: Option<u64>,
+    /// True if the image is encrypted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypted: Option<bool>,
+    /// True if the image is compressed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compressed: Option<bool>,
+    /// Name of the backing file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backing_filename: Option<String>,
+    /// Full path of the backing file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_backing_filename: Option<String>,
+    /// The format of the backing file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backing_filename_format: Option<String>,
+    /// List of VM snapshots.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshots: Option<Vec<SnapshotInfo>>,
+    /// Structure supplying additional format-specific information.
+    ///
+    /// It is kept as raw json, its shape depends on `format`. Only available
+    /// with the `json` feature.
+    #[cfg(feature = "json")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format_specific: Option<Value>,
+    /// Info of the backing image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backing_image: Option<Box<ImageInfo>>,
+}
+
+/// Information about the backing device for a block device.
+///
+/// Fields of the throttling limits keep their underscored QEMU names.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct BlockDeviceInfo {
+    /// The filename of the backing device.
+    pub file: String,
+    /// The name of the block driver node.
+    #[serde(rename = "node-name", skip_serializing_if = "Option::is_none")]
+    pub node_name: Option<String>,
+    /// True if the backing device was open read-only.
+    pub ro: bool,
+    /// The name of the block format used to open the backing device.
+    pub drv: String,
+    /// The name of the backing file (for copy-on-write).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backing_file: Option<String>,
+    /// Number of files in the backing file chain.
+    pub backing_file_depth: u64,
+    /// True if the backing device is encrypted.
+    pub encrypted: bool,
+    /// Detect and optimize zero writes.
+    pub detect_zeroes: BlockdevDetectZeroesOptions,
+    /// Total throughput limit in bytes per second.
+    pub bps: u64,
+    /// Read throughput limit in bytes per second.
+    pub bps_rd: u64,
+    /// Write throughput limit in bytes per second.
+    pub bps_wr: u64,
+    /// Total I/O operations per second.
+    pub iops: u64,
+    /// Read I/O operations per second.
+    pub iops_rd: u64,
+    /// Write I/O operations per second.
+    pub iops_wr: u64,
+    /// The info of image file.
+    pub image: ImageInfo,
+    /// Total throughput limit during bursts, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bps_max: Option<u64>,
+    /// Read throughput limit during bursts, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bps_rd_max: Option<u64>,
+    /// Write throughput limit during bursts, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bps_wr_max: Option<u64>,
+    /// Total I/O operations per second during bursts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iops_max: Option<u64>,
+    /// Read I/O operations per second during bursts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iops_rd_max: Option<u64>,
+    /// Write I/O operations per second during bursts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iops_wr_max: Option<u64>,
+    /// Maximum length of the `bps_max` burst period, in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bps_max_length: Option<u64>,
+    /// Maximum length of the `bps_rd_max` burst period, in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bps_rd_max_length: Option<u64>,
+    /// Maximum length of the `bps_wr_max` burst period, in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bps_wr_max_length: Option<u64>,
+    /// Maximum length of the `iops` burst period, in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iops_max_length: Option<u64>,
+    /// Maximum length of the `iops_rd_max` burst period, in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iops_rd_max_length: Option<u64>,
+    /// Maximum length of the `iops_wr_max` burst period, in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iops_wr_max_length: Option<u64>,
+    /// An I/O size in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iops_size: Option<u64>,
+    /// Throttle group name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// The cache mode used for the block device.
+    pub cache: BlockdevCacheInfo,
+    /// Configured write threshold for the device, 0 if disabled.
+    pub write_threshold: u64,
+    /// Dirty bitmaps information.
+    #[serde(rename = "dirty-bitmaps", skip_serializing_if = "Option::is_none")]
+    pub dirty_bitmaps: Option<Vec<BlockDirtyInfo>>,
+}
+
+/// Arguments of 'query-named-block-nodes'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct QueryNamedBlockNodesParams {
+    /// Omit the nested data about backing image ("backing-image" key) if true.
+    ///
+    /// Default is false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flat: Option<bool>,
+}
+
+impl QmpCommand for QueryNamedBlockNodesParams {
+    type Return = Vec<BlockDeviceInfo>;
+    const NAME: &'static str = "query-named-block-nodes";
+}
+
+/// Get the named block driver list.
+#[inline]
+pub fn query_named_block_nodes(flat: Option<bool>) -> Command<QueryNamedBlockNodesParams, ()> {
+    Command {
+        execute: "query-named-block-nodes".to_string(),
+        arguments: Some(QueryNamedBlockNodesParams { flat }),
+        id: None,
+    }
+}
+
+/// An enumeration of block device I/O status.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockDeviceIoStatus {
+    /// The last I/O operation has succeeded.
+    Ok,
+    /// The last I/O operation has failed.
+    Failed,
+    /// The last I/O operation has failed due to a no-space condition.
+    Nospace,
+}
+
+/// Block device information.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct BlockInfo {
+    /// The device name associated with the virtual device.
+    pub device: String,
+    /// The qdev ID, or if no ID is assigned, the QOM path of the block device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qdev: Option<String>,
+    /// This field is returned only for compatibility reasons, it should
+    /// not be used (always returns 'unknown').
+    #[serde(rename = "type")]
+    pub device_type: String,
+    /// True if the device supports removable media.
+    pub removable: bool,
+    /// True if the guest has locked this device from having its media removed.
+    pub locked: bool,
+    /// The backing device associated with this device, absent when no medium
+    /// is present.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inserted: Option<BlockDeviceInfo>,
+    /// True if the device's tray is open (only present if it has a tray).
+    #[serde(rename = "tray_open", skip_serializing_if = "Option::is_none")]
+    pub tray_open: Option<bool>,
+    /// The I/O status, only present if the device supports it and the VM is
+    /// configured to stop on errors.
+    #[serde(rename = "io-status", skip_serializing_if = "Option::is_none")]
+    pub io_status: Option<BlockDeviceIoStatus>,
+}
+
+impl BlockInfo {
+    /// Whether the inserted medium is writable.
+    ///
+    /// Returns `None` when no medium is present in the drive.
+    #[inline]
+    pub fn is_writable(&self) -> Option<bool> {
+        self.inserted.as_ref().map(|inserted| !inserted.ro)
+    }
+}
+
+/// Marker of 'query-block'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryBlock;
+
+impl QmpCommand for QueryBlock {
+    type Return = Vec<BlockInfo>;
+    const NAME: &'static str = "query-block";
+}
+
+/// Get a list of BlockInfo for all virtual block devices.
+#[inline]
+pub fn query_block() -> Command<(), ()> {
+    Command {
+        execute: "query-block".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Information about a long-running block device operation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct BlockJobInfo {
+    /// The job type.
+    #[serde(rename = "type")]
+    pub job_type: JobType,
+    /// The job identifier (originally the device name but other values
+    /// are allowed since QEMU 2.7).
+    pub device: String,
+    /// Estimated `offset` value at the completion of the job.
+    ///
+    /// This value can arbitrarily change while the job is running, in
+    /// both directions.
+    pub len: i64,
+    /// Progress made until now.
+    ///
+    /// The unit is arbitrary and the value can only meaningfully be used
+    /// for the ratio of `offset` to `len`.
+    pub offset: i64,
+    /// False if the job is known to be in a quiescent state, with no
+    /// pending I/O.
+    pub busy: bool,
+    /// Whether the job is paused or, if `busy` is true, will pause itself
+    /// as soon as possible.
+    pub paused: bool,
+    /// The rate limit, bytes per second.
+    pub speed: i64,
+    /// The status of the job (since 1.3).
+    pub io_status: BlockDeviceIoStatus,
+    /// True if the job may be completed.
+    pub ready: bool,
+    /// Current job state/status.
+    pub status: JobStatus,
+    /// Job will finalize itself when PENDING, moving to the CONCLUDED state.
+    pub auto_finalize: bool,
+    /// Job will dismiss itself when CONCLUDED, moving to the NULL state
+    /// and disappearing from the query list.
+    pub auto_dismiss: bool,
+    /// Error information if the job did not complete successfully.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BlockJobInfo {
+    /// Whether the job is ready to be completed, for example with
+    /// 'block-job-complete' on a mirror job.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.ready && self.status == JobStatus::Ready
+    }
+
+    /// Progress of the job as a fraction of its estimated length.
+    ///
+    /// Returns `None` while the length is not known yet (zero).
+    #[inline]
+    pub fn progress_fraction(&self) -> Option<f64> {
+        if self.len <= 0 {
+            return None;
+        }
+        Some(self.offset as f64 / self.len as f64)
+    }
+}
+
+/// Marker of 'query-block-jobs'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryBlockJobs;
+
+impl QmpCommand for QueryBlockJobs {
+    type Return = Vec<BlockJobInfo>;
+    const NAME: &'static str = "query-block-jobs";
+}
+
+/// Return information about long-running block device operations.
+#[inline]
+pub fn query_block_jobs() -> Command<(), ()> {
+    Command {
+        execute: "query-block-jobs".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Block latency histogram.
+///
+/// Bin `n` counts requests with latencies in `[boundaries[n-1], boundaries[n])`;
+/// the first and last bins are open-ended.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct BlockLatencyHistogramInfo {
+    /// List of interval boundary values in nanoseconds, all greater than zero
+    /// and in ascending order.
+    pub boundaries: Vec<u64>,
+    /// List of io request counts corresponding to histogram intervals,
+    /// one more element than `boundaries` has.
+    pub bins: Vec<u64>,
+}
+
+/// I/O statistics of a device over a time interval.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct BlockDeviceTimedStats {
+    /// Interval used for calculating the statistics, in seconds.
+    pub interval_length: i64,
+    /// Minimum latency of read operations in the defined interval, in nanoseconds.
+    pub min_rd_latency_ns: i64,
+    /// Maximum latency of read operations in the defined interval, in nanoseconds.
+    pub max_rd_latency_ns: i64,
+    /// Average latency of read operations in the defined interval, in nanoseconds.
+    pub avg_rd_latency_ns: i64,
+    /// Minimum latency of write operations in the defined interval, in nanoseconds.
+    pub min_wr_latency_ns: i64,
+    /// Maximum latency of write operations in the defined interval, in nanoseconds.
+    pub max_wr_latency_ns: i64,
+    /// Average latency of write operations in the defined interval, in nanoseconds.
+    pub avg_wr_latency_ns: i64,
+    /// Minimum latency of flush operations in the defined interval, in nanoseconds.
+    pub min_flush_latency_ns: i64,
+    /// Maximum latency of flush operations in the defined interval, in nanoseconds.
+    pub max_flush_latency_ns: i64,
+    /// Average latency of flush operations in the defined interval, in nanoseconds.
+    pub avg_flush_latency_ns: i64,
+    /// Average number of pending read operations in the defined interval.
+    pub avg_rd_queue_depth: f64,
+    /// Average number of pending write operations in the defined interval.
+    pub avg_wr_queue_depth: f64,
+}
+
+/// Statistics of a virtual block device or a block backing device.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct BlockDeviceStats {
+    /// The number of bytes read by the device.
+    pub rd_bytes: i64,
+    /// The number of bytes written by the device.
+    pub wr_bytes: i64,
+    /// The number of bytes unmapped by the device.
+    pub unmap_bytes: i64,
+    /// The number of read operations performed by the device.
+    pub rd_operations: i64,
+    /// The number of write operations performed by the device.
+    pub wr_operations: i64,
+    /// The number of cache flush operations performed by the device.
+    pub flush_operations: i64,
+    /// The number of unmap operations performed by the device.
+    pub unmap_operations: i64,
+    /// Total time spent on reads in nanoseconds.
+    pub rd_total_time_ns: i64,
+    /// Total time spent on writes in nanoseconds.
+    pub wr_total_time_ns: i64,
+    /// Total time spent on cache flushes in nanoseconds.
+    pub flush_total_time_ns: i64,
+    /// Total time spent on unmap operations in nanoseconds.
+    pub unmap_total_time_ns: i64,
+    /// The offset after the greatest byte written to the device.
+    pub wr_highest_offset: i64,
+    /// Number of read requests that have been merged into another request.
+    pub rd_merged: i64,
+    /// Number of write requests that have been merged into another request.
+    pub wr_merged: i64,
+    /// Number of unmap requests that have been merged into another request.
+    pub unmap_merged: i64,
+    /// Time since the last I/O operation, in nanoseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idle_time_ns: Option<i64>,
+    /// The number of failed read operations performed by the device.
+    pub failed_rd_operations: i64,
+    /// The number of failed write operations performed by the device.
+    pub failed_wr_operations: i64,
+    /// The number of failed flush operations performed by the device.
+    pub failed_flush_operations: i64,
+    /// The number of failed unmap operations performed by the device.
+    pub failed_unmap_operations: i64,
+    /// The number of invalid read operations performed by the device.
+    pub invalid_rd_operations: i64,
+    /// The number of invalid write operations performed by the device.
+    pub invalid_wr_operations: i64,
+    /// The number of invalid flush operations performed by the device.
+    pub invalid_flush_operations: i64,
+    /// The number of invalid unmap operations performed by the device.
+    pub invalid_unmap_operations: i64,
+    /// Whether invalid operations are included in the last access statistics.
+    pub account_invalid: bool,
+    /// Whether failed operations are included in the latency and last access
+    /// statistics.
+    pub account_failed: bool,
+    /// Statistics specific to the set of previously defined intervals.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timed_stats: Vec<BlockDeviceTimedStats>,
+    /// Read latency histogram, if enabled with 'block-latency-histogram-set'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rd_latency_histogram: Option<BlockLatencyHistogramInfo>,
+    /// Write latency histogram, if enabled with 'block-latency-histogram-set'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wr_latency_histogram: Option<BlockLatencyHistogramInfo>,
+    /// Flush latency histogram, if enabled with 'block-latency-histogram-set'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flush_latency_histogram: Option<BlockLatencyHistogramInfo>,
+}
+
+/// Statistics of a virtual block device or a block backing device.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct BlockStats {
+    /// If the stats are for a virtual block device, the name corresponding
+    /// to the virtual block device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// The qdev ID, or if no ID is assigned, the QOM path of the block device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qdev: Option<String>,
+    /// The node name of the device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_name: Option<String>,
+    /// The device statistics.
+    pub stats: BlockDeviceStats,
+    /// Optional driver-specific stats.
+    ///
+    /// Only available with the `json` feature.
+    #[cfg(feature = "json")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver_specific: Option<Value>,
+    /// Statistics of the protocol layer of this device, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Box<BlockStats>>,
+    /// Statistics of the backing device, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backing: Option<Box<BlockStats>>,
+}
+
+/// Arguments of 'query-blockstats'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct QueryBlockstatsParams {
+    /// If true, the command will query all the block nodes that have a node
+    /// name, in a list which will include "parent" information, but not
+    /// "backing". If false or omitted, the behavior is as before.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_nodes: Option<bool>,
+}
+
+impl QmpCommand for QueryBlockstatsParams {
+    type Return = Vec<BlockStats>;
+    const NAME: &'static str = "query-blockstats";
+}
+
+/// Query the `BlockStats` for all virtual block devices.
+#[inline]
+pub fn query_blockstats(query_nodes: Option<bool>) -> Command<QueryBlockstatsParams, ()> {
+    Command {
+        execute: "query-blockstats".to_string(),
+        arguments: Some(QueryBlockstatsParams { query_nodes }),
+        id: None,
+    }
+}
+
+/// Arguments of 'eject'.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EjectParams {
+    /// Block device name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// The name or QOM path of the guest device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// If true, eject regardless of whether the drive is locked.
+    ///
+    /// If not specified, the default value is false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force: Option<bool>,
+}
+
+impl QmpCommand for EjectParams {
+    type Return = Empty;
+    const NAME: &'static str = "eject";
+}
+
+/// Ejects the medium from a removable drive.
+///
+/// Either `device` or `id` must be set, `device` is deprecated in QEMU.
+#[inline]
+pub fn eject(
+    device: Option<String>,
+    id: Option<String>,
+    force: Option<bool>,
+) -> Command<EjectParams, ()> {
+    Command {
+        execute: "eject".to_string(),
+        arguments: Some(EjectParams { device, id, force }),
+        id: None,
+    }
+}
+
+/// Arguments of 'blockdev-open-tray'.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BlockdevOpenTrayParams {
+    /// Block device name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// The name or QOM path of the guest device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// If false (the default), an eject request will be sent to the guest
+    /// if it has locked the tray. If true, the tray will be forced open.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force: Option<bool>,
+}
+
+impl QmpCommand for BlockdevOpenTrayParams {
+    type Return = Empty;
+    const NAME: &'static str = "blockdev-open-tray";
+}
+
+/// Opens a block device's tray.
+///
+/// If there is a block driver state tree inserted as a medium, it will
+/// become inaccessible to the guest (but it will remain associated to the
+/// block device, so closing the tray will make it accessible again).
+#[inline]
+pub fn blockdev_open_tray(
+    device: Option<String>,
+    id: Option<String>,
+    force: Option<bool>,
+) -> Command<BlockdevOpenTrayParams, ()> {
+    Command {
+        execute: "blockdev-open-tray".to_string(),
+        arguments: Some(BlockdevOpenTrayParams { device, id, force }),
+        id: None,
+    }
+}
+
+/// Arguments of 'blockdev-close-tray'.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BlockdevCloseTrayParams {
+    /// Block device name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// The name or QOM path of the guest device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+impl QmpCommand for BlockdevCloseTrayParams {
+    type Return = Empty;
+    const NAME: &'static str = "blockdev-close-tray";
+}
+
+/// Closes a block device's tray.
+///
+/// If there is a block driver state tree associated with the block device
+/// (which is currently ejected), that tree will be loaded as the medium.
+#[inline]
+pub fn blockdev_close_tray(
+    device: Option<String>,
+    id: Option<String>,
+) -> Command<BlockdevCloseTrayParams, ()> {
+    Command {
+        execute: "blockdev-close-tray".to_string(),
+        arguments: Some(BlockdevCloseTrayParams { device, id }),
+        id: None,
+    }
+}
+
+/// Specifies the new read-only mode of a block device subject to the
+/// 'blockdev-change-medium' command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BlockdevChangeReadOnlyMode {
+    /// Retains the current read-only mode.
+    Retain,
+    /// Makes the device read-only.
+    ReadOnly,
+    /// Makes the device writable.
+    ReadWrite,
+}
+
+/// Arguments of 'blockdev-change-medium'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct BlockdevChangeMediumParams {
+    /// Block device name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// The name or QOM path of the guest device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Filename of the new image to be loaded.
+    pub filename: String,
+    /// Format to open the new image with.
+    ///
+    /// Defaults to the guessed format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// If false (the default), an eject request through 'blockdev-open-tray'
+    /// will be sent to the guest if it has locked the tray. If true, the
+    /// tray will be forced open.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force: Option<bool>,
+    /// Change the read-only mode of the device.
+    ///
+    /// Defaults to 'retain'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_only_mode: Option<BlockdevChangeReadOnlyMode>,
+}
+
+impl QmpCommand for BlockdevChangeMediumParams {
+    type Return = Empty;
+    const NAME: &'static str = "blockdev-change-medium";
+}
+
+/// Changes the medium inserted into a block device by ejecting the current
+/// medium and loading a new image file which is inserted as the new medium.
+#[inline]
+pub fn blockdev_change_medium(
+    params: BlockdevChangeMediumParams,
+) -> Command<BlockdevChangeMediumParams, ()> {
+    Command {
+        execute: "blockdev-change-medium".to_string(),
+        arguments: Some(params),
+        id: None,
+    }
+}
+
+/// Information about a persistent reservation manager.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct PrManagerInfo {
+    /// The identifier of the persistent reservation manager.
+    pub id: String,
+    /// True if the persistent reservation manager is connected to the
+    /// underlying storage or helper.
+    pub connected: bool,
+}
+
+/// Marker of 'query-pr-managers'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryPrManagers;
+
+impl QmpCommand for QueryPrManagers {
+    type Return = Vec<PrManagerInfo>;
+    const NAME: &'static str = "query-pr-managers";
+}
+
+/// Returns a list of information about each persistent reservation manager.
+#[inline]
+pub fn query_pr_managers() -> Command<(), ()> {
+    Command {
+        execute: "query-pr-managers".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Emitted whenever the connected status of a persistent reservation
+/// manager changes.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct PrManagerStatusChangedEvent {
+    /// The id of the PR manager object that has changed.
+    pub id: String,
+    /// True if the PR manager is connected to a backend.
+    pub connected: bool,
+}
+
+/// An enumeration of the quorum operation types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuorumOpType {
+    /// Read operation.
+    Read,
+    /// Write operation.
+    Write,
+    /// Flush operation.
+    Flush,
+}
+
+/// Emitted by the Quorum block driver if it fails to establish a quorum.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct QuorumFailureEvent {
+    /// Device name if defined else node name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// Number of the first sector of the failed read operation.
+    pub sector_num: i64,
+    /// Failed read operation sector count.
+    pub sectors_count: i64,
+}
+
+/// Emitted to report a corruption of a Quorum file.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct QuorumReportBadEvent {
+    /// Quorum operation type.
+    #[serde(rename = "type")]
+    pub op_type: QuorumOpType,
+    /// Error message.
+    ///
+    /// Only present on failure. This field contains a human-readable error
+    /// message. There are no semantics other than that the block layer
+    /// reported an error and clients should not try to interpret the error
+    /// string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The graph node name of the block driver state.
+    pub node_name: String,
+    /// Number of the first sector of the failed read operation.
+    pub sector_num: i64,
+    /// Failed read operation sector count.
+    pub sectors_count: i64,
+}
+
+/// Arguments of 'blockdev-add', kept as raw json.
+///
+/// Options of any block driver can be passed this way; see `BlockdevOptions`
+/// for a typed alternative covering the most common drivers.
+#[cfg(feature = "json")]
+#[inline]
+pub fn blockdev_add(options: Value) -> Command<Value, ()> {
+    Command {
+        execute: "blockdev-add".to_string(),
+        arguments: Some(options),
+        id: None,
+    }
+}
+
+/// Error of validating raw 'blockdev-add' options.
+#[cfg(feature = "json")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BlockdevValidationError {
+    /// The options are not a json-object.
+    NotAnObject,
+    /// The `driver` member is missing.
+    MissingDriver,
+    /// The `driver` member is not a json-string.
+    DriverNotString,
+    /// The `node-name` member is missing.
+    MissingNodeName,
+    /// The `node-name` member is not a json-string.
+    NodeNameNotString,
+}
+
+#[cfg(feature = "json")]
+impl fmt::Display for BlockdevValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BlockdevValidationError::NotAnObject => "blockdev options must be a json-object",
+            BlockdevValidationError::MissingDriver => "missing `driver` member",
+            BlockdevValidationError::DriverNotString => "`driver` member must be a json-string",
+            BlockdevValidationError::MissingNodeName => "missing `node-name` member",
+            BlockdevValidationError::NodeNameNotString => {
+                "`node-name` member must be a json-string"
+            }
+        })
+    }
+}
+
+#[cfg(all(feature = "std", feature = "json"))]
+impl std::error::Error for BlockdevValidationError {}
+
+/// Check raw 'blockdev-add' options for the members every node needs.
+///
+/// Only the presence and type of `driver` and `node-name` are checked;
+/// driver-specific options are left for the server to validate. QEMU
+/// accepts nodes without a `node-name` at the top level, but such nodes
+/// cannot be referred to afterwards.
+#[cfg(feature = "json")]
+pub fn validate_blockdev_options(options: &Value) -> Result<(), BlockdevValidationError> {
+    let object = options
+        .as_object()
+        .ok_or(BlockdevValidationError::NotAnObject)?;
+    match object.get("driver") {
+        None => return Err(BlockdevValidationError::MissingDriver),
+        Some(Value::String(_)) => {}
+        Some(_) => return Err(BlockdevValidationError::DriverNotString),
+    }
+    match object.get("node-name") {
+        None => Err(BlockdevValidationError::MissingNodeName),
+        Some(Value::String(_)) => Ok(()),
+        Some(_) => Err(BlockdevValidationError::NodeNameNotString),
+    }
+}
+
+/// Selects the AIO backend to handle I/O requests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BlockdevAioOptions {
+    /// Use qemu's thread pool.
+    #[serde(rename = "threads")]
+    Threads,
+    /// Use native AIO backend (only Linux and Windows).
+    #[serde(rename = "native")]
+    Native,
+    /// Use linux io_uring.
+    #[serde(rename = "io_uring")]
+    IoUring,
+}
+
+/// Driver specific block device options for the file backend.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct BlockdevOptionsFile {
+    /// The node name of the block driver node.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_name: Option<String>,
+    /// Whether the block device should be read-only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    /// Path to the file.
+    pub filename: String,
+    /// The id for the object that will handle persistent reservations for
+    /// this device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr_manager: Option<String>,
+    /// AIO backend.
+    ///
+    /// Defaults to 'threads'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aio: Option<BlockdevAioOptions>,
+}
+
+/// Driver specific block device options for qcow2.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct BlockdevOptionsQcow2 {
+    /// The node name of the block driver node.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_name: Option<String>,
+    /// Whether the block device should be read-only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    /// Reference to or definition of the data source block device.
+    pub file: BlockdevRef,
+    /// Reference to or definition of the backing file block device.
+    ///
+    /// `BlockdevRefOrNull::Null` disables the default backing file.
+    #[serde(
+        default,
+        deserialize_with = "some_ref_or_null",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub backing: Option<BlockdevRefOrNull>,
+    /// Whether to enable the lazy refcounts feature.
+    ///
+    /// Default is taken from the image file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lazy_refcounts: Option<bool>,
+    /// The maximum total size of the L2 table and refcount block caches in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_size: Option<u64>,
+}
+
+/// Options for creating a block device.
+///
+/// Only the most common drivers are modeled; use `blockdev_add` with raw
+/// json for the others.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "driver", rename_all = "kebab-case")]
+pub enum BlockdevOptions {
+    /// Protocol driver for host files.
+    File(BlockdevOptionsFile),
+    /// The qcow2 image format.
+    Qcow2(BlockdevOptionsQcow2),
+}
+
+impl QmpCommand for BlockdevOptions {
+    type Return = Empty;
+    const NAME: &'static str = "blockdev-add";
+}
+
+/// Reference to a block device.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BlockdevRef {
+    /// Defines a new block device inline.
+    Definition(Box<BlockdevOptions>),
+    /// References the ID of an existing block device.
+    Reference(String),
+}
+
+/// Reference to a block device, or null.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BlockdevRefOrNull {
+    /// Defines a new block device inline.
+    Definition(Box<BlockdevOptions>),
+    /// References the ID of an existing block device.
+    ///
+    /// An empty string means that no block device should be referenced.
+    Reference(String),
+    /// No block device should be referenced.
+    Null,
+}
+
+// Keeps an explicit null as `Some(BlockdevRefOrNull::Null)` instead of `None`.
+fn some_ref_or_null<'de, D>(deserializer: D) -> Result<Option<BlockdevRefOrNull>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    BlockdevRefOrNull::deserialize(deserializer).map(Some)
+}
+
+/// Creates a new block device with typed options.
+#[inline]
+pub fn blockdev_add_typed(opts: BlockdevOptions) -> Command<BlockdevOptions, ()> {
+    Command {
+        execute: "blockdev-add".to_string(),
+        arguments: Some(opts),
+        id: None,
+    }
+}
+
+/// Arguments of 'blockdev-reopen'.
+#[cfg(feature = "json")]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockdevReopenParams {
+    /// The options of each node to reopen, as for 'blockdev-add'.
+    pub options: Vec<Value>,
+}
+
+#[cfg(feature = "json")]
+impl QmpCommand for BlockdevReopenParams {
+    type Return = Empty;
+    const NAME: &'static str = "blockdev-reopen";
+}
+
+/// Reopens one or more block devices using the given set of options.
+///
+/// Any option not specified will be reset to its default value regardless
+/// of its previous status. The nodes are identified by their `node-name`,
+/// and all of them are reopened in a single transaction.
+#[cfg(feature = "json")]
+#[inline]
+pub fn blockdev_reopen(options: Vec<Value>) -> Command<BlockdevReopenParams, ()> {
+    Command {
+        execute: "blockdev-reopen".to_string(),
+        arguments: Some(BlockdevReopenParams { options }),
+        id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BlockDeviceInfo, BlockDeviceStats, BlockDeviceTimedStats, BlockInfo, BlockJobInfo,
+        BlockLatencyHistogramInfo, BlockStats, BlockdevAioOptions, BlockdevChangeMediumParams,
+        BlockdevChangeReadOnlyMode, BlockdevDetectZeroesOptions, BlockdevOptions,
+        BlockdevOptionsFile, BlockdevOptionsQcow2, BlockdevRef, BlockdevRefOrNull, JobStatus,
+        PrManagerInfo, blockdev_add_typed, blockdev_change_medium, blockdev_open_tray, eject,
+        query_block, query_block_jobs, query_blockstats, query_named_block_nodes,
+        query_pr_managers,
+    };
+    #[cfg(feature = "json")]
+    use super::{BlockdevValidationError, blockdev_reopen, validate_blockdev_options};
+    use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
+
+    #[test]
+    fn new_query_named_block_nodes() {
+        let cmd = query_named_block_nodes(Some(true));
+        let compact_string = r#"{"execute":"query-named-block-nodes","arguments":{"flat":true}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_named_block_nodes_result() {
+        let string = r#"[{"iops_rd": 0, "detect_zeroes": "off", "image": {"virtual-size": 536870912,
+            "filename": "disk1.qcow2", "cluster-size": 65536, "format": "qcow2", "actual-size": 200704,
+            "backing-filename": "base.qcow2", "full-backing-filename": "/images/base.qcow2",
+            "backing-filename-format": "qcow2", "dirty-flag": false,
+            "backing-image": {"virtual-size": 536870912, "filename": "/images/base.qcow2",
+            "format": "qcow2", "actual-size": 4096, "dirty-flag": false}},
+            "iops_wr": 0, "ro": false, "node-name": "disk1", "backing_file_depth": 1, "drv": "qcow2",
+            "iops": 0, "bps_wr": 0, "write_threshold": 0, "backing_file": "base.qcow2",
+            "encrypted": false, "bps": 0, "bps_rd": 0,
+            "cache": {"no-flush": false, "direct": false, "writeback": true},
+            "file": "disk1.qcow2"}]"#;
+        let nodes: Vec<BlockDeviceInfo> = serde_json::from_str(string).unwrap();
+        assert_eq!(nodes.len(), 1);
+        let node = &nodes[0];
+        assert_eq!(node.node_name.as_deref(), Some("disk1"));
+        assert_eq!(node.detect_zeroes, BlockdevDetectZeroesOptions::Off);
+        assert!(node.cache.writeback);
+        let backing = node.image.backing_image.as_ref().unwrap();
+        assert_eq!(backing.filename, "/images/base.qcow2");
+        assert_eq!(backing.actual_size, Some(4096));
+        assert!(backing.backing_image.is_none());
+    }
+
+    #[test]
+    fn new_eject() {
+        let cmd = eject(None, Some("ide0-1-0".to_string()), Some(true));
+        let compact_string = r#"{"execute":"eject","arguments":{"id":"ide0-1-0","force":true}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_blockdev_open_tray() {
+        let cmd = blockdev_open_tray(None, Some("ide0-1-0".to_string()), None);
+        let compact_string = r#"{"execute":"blockdev-open-tray","arguments":{"id":"ide0-1-0"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_blockdev_change_medium() {
+        let cmd = blockdev_change_medium(BlockdevChangeMediumParams {
+            device: None,
+            id: Some("ide0-1-0".to_string()),
+            filename: "/srv/images/ro.iso".to_string(),
+            format: Some("raw".to_string()),
+            force: None,
+            read_only_mode: Some(BlockdevChangeReadOnlyMode::ReadOnly),
+        });
+        let compact_string = r#"{"execute":"blockdev-change-medium","arguments":{"id":"ide0-1-0","filename":"/srv/images/ro.iso","format":"raw","read-only-mode":"read-only"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_pr_managers_result() {
+        let cmd = query_pr_managers();
+        let compact_string = r#"{"execute":"query-pr-managers"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[{"id": "pr-helper0", "connected": true}]"#;
+        let value = vec![PrManagerInfo {
+            id: "pr-helper0".to_string(),
+            connected: true,
+        }];
+        let managers: Vec<PrManagerInfo> = serde_json::from_str(string).unwrap();
+        assert_eq!(value, managers);
+    }
+
+    #[test]
+    fn new_blockdev_add_typed_file() {
+        let cmd = blockdev_add_typed(BlockdevOptions::File(BlockdevOptionsFile {
+            node_name: Some("disk0-file".to_string()),
+            read_only: None,
+            filename: "/images/disk0.img".to_string(),
+            pr_manager: None,
+            aio: Some(BlockdevAioOptions::IoUring),
+        }));
+        let raw = serde_json::json!({"execute": "blockdev-add", "arguments": {
+            "driver": "file",
+            "node-name": "disk0-file",
+            "filename": "/images/disk0.img",
+            "aio": "io_uring"
+        }});
+        assert_eq!(raw, serde_json::to_value(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_blockdev_add_typed_qcow2() {
+        let file = BlockdevOptions::File(BlockdevOptionsFile {
+            node_name: None,
+            read_only: None,
+            filename: "/images/disk1.qcow2".to_string(),
+            pr_manager: None,
+            aio: None,
+        });
+        let cmd = blockdev_add_typed(BlockdevOptions::Qcow2(BlockdevOptionsQcow2 {
+            node_name: Some("disk1".to_string()),
+            read_only: Some(false),
+            file: BlockdevRef::Definition(Box::new(file)),
+            backing: Some(BlockdevRefOrNull::Null),
+            lazy_refcounts: None,
+            cache_size: None,
+        }));
+        let raw = serde_json::json!({"execute": "blockdev-add", "arguments": {
+            "driver": "qcow2",
+            "node-name": "disk1",
+            "read-only": false,
+            "file": {"driver": "file", "filename": "/images/disk1.qcow2"},
+            "backing": null
+        }});
+        assert_eq!(raw, serde_json::to_value(&cmd).unwrap());
+        let opts: BlockdevOptions = serde_json::from_value(raw["arguments"].clone()).unwrap();
+        assert_eq!(Some(opts), cmd.arguments);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn new_blockdev_reopen() {
+        let cmd = blockdev_reopen(vec![serde_json::json!({
+            "driver": "qcow2",
+            "node-name": "disk1",
+            "file": "disk1-file",
+            "read-only": true
+        })]);
+        let compact_string = r#"{"execute":"blockdev-reopen","arguments":{"options":[{"driver":"qcow2","file":"disk1-file","node-name":"disk1","read-only":true}]}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_blockstats_result() {
+        let cmd = query_blockstats(None);
+        let compact_string = r#"{"execute":"query-blockstats","arguments":{}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[
+            {
+                "device": "ide0-hd0",
+                "node-name": "disk0",
+                "stats": {
+                    "rd_bytes": 9786368, "wr_bytes": 0, "unmap_bytes": 0,
+                    "rd_operations": 525, "wr_operations": 0,
+                    "flush_operations": 0, "unmap_operations": 0,
+                    "rd_total_time_ns": 3736717, "wr_total_time_ns": 0,
+                    "flush_total_time_ns": 0, "unmap_total_time_ns": 0,
+                    "wr_highest_offset": 0,
+                    "rd_merged": 0, "wr_merged": 0, "unmap_merged": 0,
+                    "idle_time_ns": 2953431879,
+                    "failed_rd_operations": 0, "failed_wr_operations": 0,
+                    "failed_flush_operations": 0, "failed_unmap_operations": 0,
+                    "invalid_rd_operations": 0, "invalid_wr_operations": 0,
+                    "invalid_flush_operations": 0, "invalid_unmap_operations": 0,
+                    "account_invalid": true, "account_failed": false,
+                    "timed_stats": [
+                        {
+                            "interval_length": 60,
+                            "min_rd_latency_ns": 1000, "max_rd_latency_ns": 9000,
+                            "avg_rd_latency_ns": 7117,
+                            "min_wr_latency_ns": 0, "max_wr_latency_ns": 0,
+                            "avg_wr_latency_ns": 0,
+                            "min_flush_latency_ns": 0, "max_flush_latency_ns": 0,
+                            "avg_flush_latency_ns": 0,
+                            "avg_rd_queue_depth": 0.5, "avg_wr_queue_depth": 0.0
+                        }
+                    ],
+                    "rd_latency_histogram": {
+                        "boundaries": [10000, 50000],
+                        "bins": [500, 20, 5]
+                    }
+                },
+                "qdev": "/machine/unattached/device[23]"
+            }
+        ]"#;
+        let value = vec![BlockStats {
+            device: Some("ide0-hd0".to_string()),
+            qdev: Some("/machine/unattached/device[23]".to_string()),
+            node_name: Some("disk0".to_string()),
+            stats: BlockDeviceStats {
+                rd_bytes: 9786368,
+                wr_bytes: 0,
+                unmap_bytes: 0,
+                rd_operations: 525,
+                wr_operations: 0,
+                flush_operations: 0,
+                unmap_operations: 0,
+                rd_total_time_ns: 3736717,
+                wr_total_time_ns: 0,
+                flush_total_time_ns: 0,
+                unmap_total_time_ns: 0,
+                wr_highest_offset: 0,
+                rd_merged: 0,
+                wr_merged: 0,
+                unmap_merged: 0,
+                idle_time_ns: Some(2953431879),
+                failed_rd_operations: 0,
+                failed_wr_operations: 0,
+                failed_flush_operations: 0,
+                failed_unmap_operations: 0,
+                invalid_rd_operations: 0,
+                invalid_wr_operations: 0,
+                invalid_flush_operations: 0,
+                invalid_unmap_operations: 0,
+                account_invalid: true,
+                account_failed: false,
+                timed_stats: vec![BlockDeviceTimedStats {
+                    interval_length: 60,
+                    min_rd_latency_ns: 1000,
+                    max_rd_latency_ns: 9000,
+                    avg_rd_latency_ns: 7117,
+                    min_wr_latency_ns: 0,
+                    max_wr_latency_ns: 0,
+                    avg_wr_latency_ns: 0,
+                    min_flush_latency_ns: 0,
+                    max_flush_latency_ns: 0,
+                    avg_flush_latency_ns: 0,
+                    avg_rd_queue_depth: 0.5,
+                    avg_wr_queue_depth: 0.0,
+                }],
+                rd_latency_histogram: Some(BlockLatencyHistogramInfo {
+                    boundaries: vec![10000, 50000],
+                    bins: vec![500, 20, 5],
+                }),
+                wr_latency_histogram: None,
+                flush_latency_histogram: None,
+            },
+            #[cfg(feature = "json")]
+            driver_specific: None,
+            parent: None,
+            backing: None,
+        }];
+        assert_eq!(
+            value,
+            serde_json::from_str::<Vec<BlockStats>>(string).unwrap()
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn validate_blockdev_options_members() {
+        let valid = serde_json::json!({
+            "driver": "qcow2",
+            "node-name": "disk0",
+            "file": {"driver": "file", "filename": "disk.qcow2"}
+        });
+        assert_eq!(validate_blockdev_options(&valid), Ok(()));
+        let missing_driver = serde_json::json!({"node-name": "disk0"});
+        assert_eq!(
+            validate_blockdev_options(&missing_driver),
+            Err(BlockdevValidationError::MissingDriver)
+        );
+        let driver_not_string = serde_json::json!({"driver": 42, "node-name": "disk0"});
+        assert_eq!(
+            validate_blockdev_options(&driver_not_string),
+            Err(BlockdevValidationError::DriverNotString)
+        );
+        let missing_node_name = serde_json::json!({"driver": "null-co"});
+        assert_eq!(
+            validate_blockdev_options(&missing_node_name),
+            Err(BlockdevValidationError::MissingNodeName)
+        );
+        assert_eq!(
+            validate_blockdev_options(&serde_json::json!(["qcow2"])),
+            Err(BlockdevValidationError::NotAnObject)
+        );
+    }
+
+    #[test]
+    fn query_block_result() {
+        let cmd = query_block();
+        let compact_string = r#"{"execute":"query-block"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[{"device": "ide0-hd0", "locked": false, "removable": false,
+            "inserted": {"ro": false, "drv": "qcow2", "encrypted": false, "file": "disk0.qcow2",
+            "backing_file_depth": 0, "bps": 0, "bps_rd": 0, "bps_wr": 0, "iops": 0,
+            "iops_rd": 0, "iops_wr": 0, "detect_zeroes": "off", "write_threshold": 0,
+            "image": {"filename": "disk0.qcow2", "format": "qcow2", "virtual-size": 2048000},
+            "cache": {"no-flush": false, "direct": false, "writeback": true}},
+            "qdev": "ide_disk", "type": "unknown"},
+            {"device": "ide1-cd0", "locked": false, "removable": true, "tray_open": false,
+            "inserted": {"ro": true, "drv": "raw", "encrypted": false, "file": "cdrom.iso",
+            "backing_file_depth": 0, "bps": 0, "bps_rd": 0, "bps_wr": 0, "iops": 0,
+            "iops_rd": 0, "iops_wr": 0, "detect_zeroes": "off", "write_threshold": 0,
+            "image": {"filename": "cdrom.iso", "format": "raw", "virtual-size": 4096},
+            "cache": {"no-flush": false, "direct": false, "writeback": true}},
+            "qdev": "/machine/unattached/device[23]", "io-status": "ok", "type": "unknown"},
+            {"device": "floppy0", "locked": false, "removable": true,
+            "qdev": "/machine/unattached/device[20]", "type": "unknown"}]"#;
+        let blocks: Vec<BlockInfo> = serde_json::from_str(string).unwrap();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].is_writable(), Some(true));
+        assert_eq!(blocks[1].is_writable(), Some(false));
+        assert_eq!(blocks[2].is_writable(), None);
+        assert_eq!(blocks[2].device_type, "unknown");
+    }
+
+    #[test]
+    fn query_block_jobs_result() {
+        let cmd = query_block_jobs();
+        let compact_string = r#"{"execute":"query-block-jobs"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[{"auto-finalize": true, "io-status": "ok", "device": "mirror0",
+            "auto-dismiss": true, "busy": false, "len": 2147483648, "offset": 2147483648,
+            "status": "ready", "paused": false, "speed": 0, "ready": true, "type": "mirror"},
+            {"auto-finalize": true, "io-status": "ok", "device": "mirror1",
+            "auto-dismiss": true, "busy": true, "len": 4096, "offset": 1024,
+            "status": "running", "paused": false, "speed": 0, "ready": false, "type": "mirror"}]"#;
+        let jobs: Vec<BlockJobInfo> = serde_json::from_str(string).unwrap();
+        assert!(jobs[0].is_ready());
+        assert_eq!(jobs[0].progress_fraction(), Some(1.0));
+        assert!(!jobs[1].is_ready());
+        assert_eq!(jobs[1].progress_fraction(), Some(0.25));
+        let mut standby = jobs[0].clone();
+        standby.status = JobStatus::Standby;
+        assert!(!standby.is_ready());
+        standby.len = 0;
+        assert_eq!(standby.progress_fraction(), None);
+    }
+}
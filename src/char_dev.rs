@@ -0,0 +1,314 @@
+//! Character devices.
+use alloc::string::{String, ToString};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "json")]
+use serde_json::{Map, Value};
+
+use crate::generic::{Command, QmpCommand};
+use crate::sockets::SocketAddressLegacy;
+
+/// Emitted when the guest opens or closes a virtio-serial port.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct VserportChangeEvent {
+    /// Device identifier of the virtio-serial port.
+    pub id: String,
+    /// True if the guest has opened the virtio-serial port.
+    pub open: bool,
+}
+
+/// Configuration shared across all chardev backends.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChardevCommon {
+    /// The name of a logfile to save output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logfile: Option<String>,
+    /// True to append instead of truncate (default to false to truncate).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logappend: Option<bool>,
+}
+
+/// Configuration info for file chardevs.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChardevFile {
+    /// The name of a logfile to save output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logfile: Option<String>,
+    /// True to append instead of truncate (default to false to truncate).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logappend: Option<bool>,
+    /// The name of the input file.
+    #[serde(rename = "in", skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    /// The name of the output file.
+    pub out: String,
+    /// Open the file in append mode (default false to truncate).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub append: Option<bool>,
+}
+
+/// Configuration info for device and pipe chardevs.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChardevHostdev {
+    /// The name of a logfile to save output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logfile: Option<String>,
+    /// True to append instead of truncate (default to false to truncate).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logappend: Option<bool>,
+    /// The name of the special file for the device, i.e. /dev/ttyS0 on
+    /// Unix or COM1: on Windows.
+    pub device: String,
+}
+
+/// Configuration info for (stream) socket chardevs.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ChardevSocket {
+    /// The name of a logfile to save output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logfile: Option<String>,
+    /// True to append instead of truncate (default to false to truncate).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logappend: Option<bool>,
+    /// Socket address to listen on (server=true) or connect to (server=false).
+    pub addr: SocketAddressLegacy,
+    /// The ID of the TLS credentials object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_creds: Option<String>,
+    /// The ID of the QAuthZ authorization object against which the client's
+    /// x509 distinguished name will be validated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_authz: Option<String>,
+    /// Create server socket (default: true).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server: Option<bool>,
+    /// Wait for incoming connection on server sockets (default: false).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait: Option<bool>,
+    /// Set TCP_NODELAY socket option (default: false).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nodelay: Option<bool>,
+    /// Enable telnet protocol on server sockets (default: false).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telnet: Option<bool>,
+    /// Enable tn3270 protocol on server sockets (default: false).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tn3270: Option<bool>,
+    /// Enable websocket protocol on server sockets (default: false).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub websocket: Option<bool>,
+    /// For a client socket, if a socket is disconnected, then attempt a
+    /// reconnect after the given number of milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reconnect_ms: Option<u64>,
+}
+
+/// Configuration info for stdio chardevs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChardevStdio {
+    /// The name of a logfile to save output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logfile: Option<String>,
+    /// True to append instead of truncate (default to false to truncate).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logappend: Option<bool>,
+    /// Allow signals (such as SIGINT triggered by ^C) be delivered to QEMU.
+    ///
+    /// Default: true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signal: Option<bool>,
+}
+
+/// Configuration info for virtual console chardevs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChardevVc {
+    /// The name of a logfile to save output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logfile: Option<String>,
+    /// True to append instead of truncate (default to false to truncate).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logappend: Option<bool>,
+    /// Console width, in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<i64>,
+    /// Console height, in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<i64>,
+    /// Console width, in chars.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cols: Option<i64>,
+    /// Console height, in chars.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rows: Option<i64>,
+}
+
+/// Configuration info for ring buffer chardevs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChardevRingbuf {
+    /// The name of a logfile to save output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logfile: Option<String>,
+    /// True to append instead of truncate (default to false to truncate).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logappend: Option<bool>,
+    /// Ring buffer size, must be power of two, default is 65536.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<i64>,
+}
+
+/// Configuration info for the new chardev backend.
+///
+/// Only the most common backends are modeled; use `chardev_add` with raw
+/// json for the others.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "kebab-case")]
+pub enum ChardevBackend {
+    /// Regular files.
+    File(ChardevFile),
+    /// Serial host device.
+    Serial(ChardevHostdev),
+    /// Pipes.
+    Pipe(ChardevHostdev),
+    /// Stream socket.
+    Socket(ChardevSocket),
+    /// Pseudo-terminal.
+    Pty(ChardevCommon),
+    /// No-op chardev.
+    Null(ChardevCommon),
+    /// Standard I/O.
+    Stdio(ChardevStdio),
+    /// Virtual console.
+    Vc(ChardevVc),
+    /// Memory ring buffer.
+    Ringbuf(ChardevRingbuf),
+}
+
+/// Return info about the chardev backend just created.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ChardevReturn {
+    /// Name of the slave pseudoterminal device, present if and only if a
+    /// chardev of type 'pty' was created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pty: Option<String>,
+}
+
+/// Arguments of 'chardev-add'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChardevAddParams {
+    /// The chardev's ID, must be unique.
+    pub id: String,
+    /// Backend type and parameters.
+    pub backend: ChardevBackend,
+}
+
+impl QmpCommand for ChardevAddParams {
+    type Return = ChardevReturn;
+    const NAME: &'static str = "chardev-add";
+}
+
+/// Arguments of 'chardev-add', with the backend kept as raw json.
+///
+/// Any backend can be passed this way; see `ChardevBackend` for a typed
+/// alternative covering the most common backends.
+#[cfg(feature = "json")]
+#[inline]
+pub fn chardev_add(id: String, backend: Value) -> Command<Value, ()> {
+    let mut arguments = Map::new();
+    arguments.insert("id".to_string(), Value::String(id));
+    arguments.insert("backend".to_string(), backend);
+    Command {
+        execute: "chardev-add".to_string(),
+        arguments: Some(Value::Object(arguments)),
+        id: None,
+    }
+}
+
+/// Add a character device backend with a typed backend.
+#[inline]
+pub fn chardev_add_typed(id: String, backend: ChardevBackend) -> Command<ChardevAddParams, ()> {
+    Command {
+        execute: "chardev-add".to_string(),
+        arguments: Some(ChardevAddParams { id, backend }),
+        id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "json")]
+    use super::chardev_add;
+    use super::{
+        ChardevBackend, ChardevCommon, ChardevFile, ChardevReturn, ChardevSocket, chardev_add_typed,
+    };
+    use crate::sockets::{SocketAddressLegacy, UnixSocketAddress};
+    use alloc::string::ToString;
+
+    #[test]
+    fn new_chardev_add_typed_socket() {
+        let cmd = chardev_add_typed(
+            "mon1".to_string(),
+            ChardevBackend::Socket(ChardevSocket {
+                logfile: None,
+                logappend: None,
+                addr: SocketAddressLegacy::Unix(UnixSocketAddress {
+                    path: "/tmp/mon1.sock".to_string(),
+                }),
+                tls_creds: None,
+                tls_authz: None,
+                server: Some(true),
+                wait: Some(false),
+                nodelay: None,
+                telnet: None,
+                tn3270: None,
+                websocket: None,
+                reconnect_ms: None,
+            }),
+        );
+        let compact_string = r#"{"execute":"chardev-add","arguments":{"id":"mon1","backend":{"type":"socket","data":{"addr":{"type":"unix","data":{"path":"/tmp/mon1.sock"}},"server":true,"wait":false}}}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        #[cfg(feature = "json")]
+        {
+            let raw = chardev_add(
+                "mon1".to_string(),
+                serde_json::json!({
+                    "type": "socket",
+                    "data": {
+                        "addr": {"type": "unix", "data": {"path": "/tmp/mon1.sock"}},
+                        "server": true,
+                        "wait": false
+                    }
+                }),
+            );
+            assert_eq!(
+                serde_json::to_value(&raw).unwrap(),
+                serde_json::to_value(&cmd).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn new_chardev_add_typed_file_and_pty() {
+        let cmd = chardev_add_typed(
+            "log0".to_string(),
+            ChardevBackend::File(ChardevFile {
+                logfile: None,
+                logappend: None,
+                input: None,
+                out: "/tmp/serial.log".to_string(),
+                append: Some(true),
+            }),
+        );
+        let compact_string = r#"{"execute":"chardev-add","arguments":{"id":"log0","backend":{"type":"file","data":{"out":"/tmp/serial.log","append":true}}}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let cmd = chardev_add_typed(
+            "pty0".to_string(),
+            ChardevBackend::Pty(ChardevCommon::default()),
+        );
+        let compact_string = r#"{"execute":"chardev-add","arguments":{"id":"pty0","backend":{"type":"pty","data":{}}}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let result: ChardevReturn = serde_json::from_str(r#"{"pty": "/dev/pts/1"}"#).unwrap();
+        assert_eq!(result.pty.as_deref(), Some("/dev/pts/1"));
+    }
+}
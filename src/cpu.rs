@@ -0,0 +1,169 @@
+//! CPU models.
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::generic::{Command, QmpCommand};
+
+/// Virtual CPU model.
+///
+/// A CPU model consists of the name of a CPU definition, to which delta
+/// changes are applied (e.g. features added/removed).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct CpuModelInfo {
+    /// The name of the CPU definition the model is based on.
+    pub name: String,
+    /// A dictionary of QOM properties to be applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub props: Option<Map<String, Value>>,
+}
+
+/// An enumeration of CPU model comparison results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CpuModelCompareResult {
+    /// If model A is incompatible to model B, model A is not guaranteed
+    /// to run where model B runs and the other way around.
+    Incompatible,
+    /// If model A is identical to model B, model A is guaranteed to run
+    /// where model B runs and the other way around.
+    Identical,
+    /// If model A is a superset of model B, model B is guaranteed to run
+    /// where model A runs. There are no guarantees about the other way.
+    Superset,
+    /// If model A is a subset of model B, model A is guaranteed to run
+    /// where model B runs. There are no guarantees about the other way.
+    Subset,
+}
+
+/// The result of a CPU model comparison.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct CpuModelCompareInfo {
+    /// The result of the compare operation.
+    pub result: CpuModelCompareResult,
+    /// List of properties that led to the comparison result not being identical.
+    pub responsible_properties: Vec<String>,
+}
+
+/// Arguments of 'query-cpu-model-comparison'.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryCpuModelComparisonParams {
+    /// The first CPU model.
+    pub modela: CpuModelInfo,
+    /// The second CPU model.
+    pub modelb: CpuModelInfo,
+}
+
+impl QmpCommand for QueryCpuModelComparisonParams {
+    type Return = CpuModelCompareInfo;
+    const NAME: &'static str = "query-cpu-model-comparison";
+}
+
+/// Compares two CPU models, returning how they compare in a specific
+/// configuration.
+///
+/// The results indicates how both models compare regarding runnability.
+#[inline]
+pub fn query_cpu_model_comparison(
+    modela: CpuModelInfo,
+    modelb: CpuModelInfo,
+) -> Command<QueryCpuModelComparisonParams, ()> {
+    Command {
+        execute: "query-cpu-model-comparison".to_string(),
+        arguments: Some(QueryCpuModelComparisonParams { modela, modelb }),
+        id: None,
+    }
+}
+
+/// The result of a CPU model baseline.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct CpuModelBaselineInfo {
+    /// The baselined CPU model.
+    pub model: CpuModelInfo,
+}
+
+/// Arguments of 'query-cpu-model-baseline'.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryCpuModelBaselineParams {
+    /// The first CPU model.
+    pub modela: CpuModelInfo,
+    /// The second CPU model.
+    pub modelb: CpuModelInfo,
+}
+
+impl QmpCommand for QueryCpuModelBaselineParams {
+    type Return = CpuModelBaselineInfo;
+    const NAME: &'static str = "query-cpu-model-baseline";
+}
+
+/// Baseline two CPU models, creating a compatible third model.
+///
+/// The created model will always be a static, migration-safe CPU model.
+#[inline]
+pub fn query_cpu_model_baseline(
+    modela: CpuModelInfo,
+    modelb: CpuModelInfo,
+) -> Command<QueryCpuModelBaselineParams, ()> {
+    Command {
+        execute: "query-cpu-model-baseline".to_string(),
+        arguments: Some(QueryCpuModelBaselineParams { modela, modelb }),
+        id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CpuModelCompareInfo, CpuModelCompareResult, CpuModelInfo, query_cpu_model_baseline,
+        query_cpu_model_comparison,
+    };
+    use alloc::{string::ToString, vec};
+    use serde_json::Map;
+
+    fn models() -> (CpuModelInfo, CpuModelInfo) {
+        let mut props = Map::new();
+        props.insert("vx".to_string(), false.into());
+        let modela = CpuModelInfo {
+            name: "z13-base".to_string(),
+            props: Some(props),
+        };
+        let modelb = CpuModelInfo {
+            name: "z14".to_string(),
+            props: None,
+        };
+        (modela, modelb)
+    }
+
+    #[test]
+    fn new_query_cpu_model_comparison() {
+        let (modela, modelb) = models();
+        let cmd = query_cpu_model_comparison(modela, modelb);
+        let compact_string = r#"{"execute":"query-cpu-model-comparison","arguments":{"modela":{"name":"z13-base","props":{"vx":false}},"modelb":{"name":"z14"}}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_query_cpu_model_baseline() {
+        let (modela, modelb) = models();
+        let cmd = query_cpu_model_baseline(modela, modelb);
+        let compact_string = r#"{"execute":"query-cpu-model-baseline","arguments":{"modela":{"name":"z13-base","props":{"vx":false}},"modelb":{"name":"z14"}}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_cpu_model_comparison_result() {
+        let string = r#"{"result": "subset", "responsible-properties": ["vx", "gs"]}"#;
+        let value = CpuModelCompareInfo {
+            result: CpuModelCompareResult::Subset,
+            responsible_properties: vec!["vx".to_string(), "gs".to_string()],
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+}
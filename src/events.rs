@@ -0,0 +1,1110 @@
+//! Asynchronous event dispatch.
+//!
+//! Events are decoded from their generic `Event` form into `AnyEvent`
+//! according to the event name.
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer, de::DeserializeOwned, ser::SerializeStruct,
+};
+use serde_json::{Map, Value};
+
+use crate::acpi::AcpiDeviceOstEvent;
+use crate::block::{PrManagerStatusChangedEvent, QuorumFailureEvent, QuorumReportBadEvent};
+use crate::char_dev::VserportChangeEvent;
+use crate::generic::{Event, Timestamp};
+use crate::job::{JobInfo, JobStatus, JobStatusChangeEvent};
+use crate::machine::MemoryDeviceSizeChangeEvent;
+use crate::migration::{MigrationPassEvent, UnplugPrimaryEvent};
+use crate::misc::RtcChangeEvent;
+use crate::net::FailoverNegotiatedEvent;
+use crate::qdev::DeviceUnplugGuestErrorEvent;
+use crate::ui::{SpiceConnectedEvent, SpiceDisconnectedEvent, SpiceInitializedEvent};
+use crate::vm_run_state::{
+    GuestCrashloadedEvent, GuestPanicAction, GuestPanickedEvent, ResetEvent, ShutdownEvent,
+};
+
+/// An asynchronous event of any known type.
+///
+/// Events not modeled by this library are kept as `AnyEvent::Other`. Events
+/// serialize back into the wire form of `Event`, so that they can be stored
+/// and replayed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AnyEvent {
+    /// 'ACPI_DEVICE_OST' event.
+    AcpiDeviceOst {
+        /// Event data.
+        data: AcpiDeviceOstEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'VSERPORT_CHANGE' event.
+    VserportChange {
+        /// Event data.
+        data: VserportChangeEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'GUEST_PANICKED' event.
+    GuestPanicked {
+        /// Event data.
+        data: GuestPanickedEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'GUEST_CRASHLOADED' event.
+    GuestCrashloaded {
+        /// Event data.
+        data: GuestCrashloadedEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'SHUTDOWN' event.
+    Shutdown {
+        /// Event data.
+        data: ShutdownEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'POWERDOWN' event.
+    Powerdown {
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'RESET' event.
+    Reset {
+        /// Event data.
+        data: ResetEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'STOP' event.
+    Stop {
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'RESUME' event.
+    Resume {
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'MIGRATION_PASS' event.
+    MigrationPass {
+        /// Event data.
+        data: MigrationPassEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'PR_MANAGER_STATUS_CHANGED' event.
+    PrManagerStatusChanged {
+        /// Event data.
+        data: PrManagerStatusChangedEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'UNPLUG_PRIMARY' event.
+    UnplugPrimary {
+        /// Event data.
+        data: UnplugPrimaryEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'FAILOVER_NEGOTIATED' event.
+    FailoverNegotiated {
+        /// Event data.
+        data: FailoverNegotiatedEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'QUORUM_FAILURE' event.
+    QuorumFailure {
+        /// Event data.
+        data: QuorumFailureEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'QUORUM_REPORT_BAD' event.
+    QuorumReportBad {
+        /// Event data.
+        data: QuorumReportBadEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'MEMORY_DEVICE_SIZE_CHANGE' event.
+    MemoryDeviceSizeChange {
+        /// Event data.
+        data: MemoryDeviceSizeChangeEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'DEVICE_UNPLUG_GUEST_ERROR' event.
+    DeviceUnplugGuestError {
+        /// Event data.
+        data: DeviceUnplugGuestErrorEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'RTC_CHANGE' event.
+    RtcChange {
+        /// Event data.
+        data: RtcChangeEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'JOB_STATUS_CHANGE' event.
+    JobStatusChange {
+        /// Event data.
+        data: JobStatusChangeEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'SPICE_CONNECTED' event.
+    SpiceConnected {
+        /// Event data.
+        data: SpiceConnectedEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'SPICE_INITIALIZED' event.
+    SpiceInitialized {
+        /// Event data.
+        data: SpiceInitializedEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'SPICE_DISCONNECTED' event.
+    SpiceDisconnected {
+        /// Event data.
+        data: SpiceDisconnectedEvent,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// 'SPICE_MIGRATE_COMPLETED' event.
+    SpiceMigrateCompleted {
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+    /// An event not modeled by this library.
+    Other {
+        /// Name of the event.
+        event: String,
+        /// Raw event data, if any.
+        data: Option<Value>,
+        /// Time when the event occurred.
+        timestamp: Timestamp,
+    },
+}
+
+impl AnyEvent {
+    /// Decode a generic event according to its name.
+    ///
+    /// Fails if a known event carries data not matching its structure.
+    pub fn from_event(event: Event<Value>) -> Result<Self, serde_json::Error> {
+        let Event {
+            event,
+            data,
+            timestamp,
+        } = event;
+        Ok(match event.as_str() {
+            "ACPI_DEVICE_OST" => AnyEvent::AcpiDeviceOst {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "VSERPORT_CHANGE" => AnyEvent::VserportChange {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "GUEST_PANICKED" => AnyEvent::GuestPanicked {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "GUEST_CRASHLOADED" => AnyEvent::GuestCrashloaded {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "SHUTDOWN" => AnyEvent::Shutdown {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "POWERDOWN" => AnyEvent::Powerdown { timestamp },
+            "RESET" => AnyEvent::Reset {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "STOP" => AnyEvent::Stop { timestamp },
+            "RESUME" => AnyEvent::Resume { timestamp },
+            "MIGRATION_PASS" => AnyEvent::MigrationPass {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "PR_MANAGER_STATUS_CHANGED" => AnyEvent::PrManagerStatusChanged {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "UNPLUG_PRIMARY" => AnyEvent::UnplugPrimary {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "FAILOVER_NEGOTIATED" => AnyEvent::FailoverNegotiated {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "QUORUM_FAILURE" => AnyEvent::QuorumFailure {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "QUORUM_REPORT_BAD" => AnyEvent::QuorumReportBad {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "MEMORY_DEVICE_SIZE_CHANGE" => AnyEvent::MemoryDeviceSizeChange {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "DEVICE_UNPLUG_GUEST_ERROR" => AnyEvent::DeviceUnplugGuestError {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "RTC_CHANGE" => AnyEvent::RtcChange {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "JOB_STATUS_CHANGE" => AnyEvent::JobStatusChange {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "SPICE_CONNECTED" => AnyEvent::SpiceConnected {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "SPICE_INITIALIZED" => AnyEvent::SpiceInitialized {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "SPICE_DISCONNECTED" => AnyEvent::SpiceDisconnected {
+                data: decode_data(data)?,
+                timestamp,
+            },
+            "SPICE_MIGRATE_COMPLETED" => AnyEvent::SpiceMigrateCompleted { timestamp },
+            _ => AnyEvent::Other {
+                event,
+                data,
+                timestamp,
+            },
+        })
+    }
+
+    /// Name of the event, as sent in the `event` member.
+    pub fn name(&self) -> &str {
+        match self {
+            AnyEvent::AcpiDeviceOst { .. } => "ACPI_DEVICE_OST",
+            AnyEvent::VserportChange { .. } => "VSERPORT_CHANGE",
+            AnyEvent::GuestPanicked { .. } => "GUEST_PANICKED",
+            AnyEvent::GuestCrashloaded { .. } => "GUEST_CRASHLOADED",
+            AnyEvent::Shutdown { .. } => "SHUTDOWN",
+            AnyEvent::Powerdown { .. } => "POWERDOWN",
+            AnyEvent::Reset { .. } => "RESET",
+            AnyEvent::Stop { .. } => "STOP",
+            AnyEvent::Resume { .. } => "RESUME",
+            AnyEvent::MigrationPass { .. } => "MIGRATION_PASS",
+            AnyEvent::PrManagerStatusChanged { .. } => "PR_MANAGER_STATUS_CHANGED",
+            AnyEvent::UnplugPrimary { .. } => "UNPLUG_PRIMARY",
+            AnyEvent::FailoverNegotiated { .. } => "FAILOVER_NEGOTIATED",
+            AnyEvent::QuorumFailure { .. } => "QUORUM_FAILURE",
+            AnyEvent::QuorumReportBad { .. } => "QUORUM_REPORT_BAD",
+            AnyEvent::MemoryDeviceSizeChange { .. } => "MEMORY_DEVICE_SIZE_CHANGE",
+            AnyEvent::DeviceUnplugGuestError { .. } => "DEVICE_UNPLUG_GUEST_ERROR",
+            AnyEvent::RtcChange { .. } => "RTC_CHANGE",
+            AnyEvent::JobStatusChange { .. } => "JOB_STATUS_CHANGE",
+            AnyEvent::SpiceConnected { .. } => "SPICE_CONNECTED",
+            AnyEvent::SpiceInitialized { .. } => "SPICE_INITIALIZED",
+            AnyEvent::SpiceDisconnected { .. } => "SPICE_DISCONNECTED",
+            AnyEvent::SpiceMigrateCompleted { .. } => "SPICE_MIGRATE_COMPLETED",
+            AnyEvent::Other { event, .. } => event,
+        }
+    }
+
+    /// Time when the event occurred.
+    pub fn timestamp(&self) -> &Timestamp {
+        match self {
+            AnyEvent::AcpiDeviceOst { timestamp, .. }
+            | AnyEvent::VserportChange { timestamp, .. }
+            | AnyEvent::GuestPanicked { timestamp, .. }
+            | AnyEvent::GuestCrashloaded { timestamp, .. }
+            | AnyEvent::Shutdown { timestamp, .. }
+            | AnyEvent::Powerdown { timestamp, .. }
+            | AnyEvent::Reset { timestamp, .. }
+            | AnyEvent::Stop { timestamp, .. }
+            | AnyEvent::Resume { timestamp, .. }
+            | AnyEvent::MigrationPass { timestamp, .. }
+            | AnyEvent::PrManagerStatusChanged { timestamp, .. }
+            | AnyEvent::UnplugPrimary { timestamp, .. }
+            | AnyEvent::FailoverNegotiated { timestamp, .. }
+            | AnyEvent::QuorumFailure { timestamp, .. }
+            | AnyEvent::QuorumReportBad { timestamp, .. }
+            | AnyEvent::MemoryDeviceSizeChange { timestamp, .. }
+            | AnyEvent::DeviceUnplugGuestError { timestamp, .. }
+            | AnyEvent::RtcChange { timestamp, .. }
+            | AnyEvent::JobStatusChange { timestamp, .. }
+            | AnyEvent::SpiceConnected { timestamp, .. }
+            | AnyEvent::SpiceInitialized { timestamp, .. }
+            | AnyEvent::SpiceDisconnected { timestamp, .. }
+            | AnyEvent::SpiceMigrateCompleted { timestamp, .. }
+            | AnyEvent::Other { timestamp, .. } => timestamp,
+        }
+    }
+
+    /// Returns whether this event indicates that the virtual machine stops executing for good.
+    ///
+    /// This is the case for 'SHUTDOWN', and for 'GUEST_PANICKED' when the
+    /// emulator powers off. Note that if the command-line option "-no-shutdown"
+    /// has been specified, qemu will not exit after a 'SHUTDOWN' event.
+    pub fn is_terminal(&self) -> bool {
+        match self {
+            AnyEvent::Shutdown { .. } => true,
+            AnyEvent::GuestPanicked { data, .. } => data.action == GuestPanicAction::Poweroff,
+            _ => false,
+        }
+    }
+
+    /// Decode the event data into a caller-defined type.
+    ///
+    /// Raw data of `AnyEvent::Other` events is parsed directly, while data of
+    /// known events is re-serialized first. Returns `None` if the event
+    /// carries no data.
+    pub fn data_as<T: DeserializeOwned>(&self) -> Option<serde_json::Result<T>> {
+        let value = match self {
+            AnyEvent::AcpiDeviceOst { data, .. } => serde_json::to_value(data),
+            AnyEvent::VserportChange { data, .. } => serde_json::to_value(data),
+            AnyEvent::GuestPanicked { data, .. } => serde_json::to_value(data),
+            AnyEvent::GuestCrashloaded { data, .. } => serde_json::to_value(data),
+            AnyEvent::Shutdown { data, .. } => serde_json::to_value(data),
+            AnyEvent::Reset { data, .. } => serde_json::to_value(data),
+            AnyEvent::MigrationPass { data, .. } => serde_json::to_value(data),
+            AnyEvent::PrManagerStatusChanged { data, .. } => serde_json::to_value(data),
+            AnyEvent::UnplugPrimary { data, .. } => serde_json::to_value(data),
+            AnyEvent::FailoverNegotiated { data, .. } => serde_json::to_value(data),
+            AnyEvent::QuorumFailure { data, .. } => serde_json::to_value(data),
+            AnyEvent::QuorumReportBad { data, .. } => serde_json::to_value(data),
+            AnyEvent::MemoryDeviceSizeChange { data, .. } => serde_json::to_value(data),
+            AnyEvent::DeviceUnplugGuestError { data, .. } => serde_json::to_value(data),
+            AnyEvent::RtcChange { data, .. } => serde_json::to_value(data),
+            AnyEvent::JobStatusChange { data, .. } => serde_json::to_value(data),
+            AnyEvent::SpiceConnected { data, .. } => serde_json::to_value(data),
+            AnyEvent::SpiceInitialized { data, .. } => serde_json::to_value(data),
+            AnyEvent::SpiceDisconnected { data, .. } => serde_json::to_value(data),
+            AnyEvent::Other { data, .. } => {
+                return data.as_ref().map(T::deserialize);
+            }
+            AnyEvent::Powerdown { .. }
+            | AnyEvent::Stop { .. }
+            | AnyEvent::Resume { .. }
+            | AnyEvent::SpiceMigrateCompleted { .. } => {
+                return None;
+            }
+        };
+        Some(value.and_then(serde_json::from_value))
+    }
+}
+
+impl<'de> Deserialize<'de> for AnyEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let event = Event::<Value>::deserialize(deserializer)?;
+        AnyEvent::from_event(event).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for AnyEvent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Event", 3)?;
+        state.serialize_field("event", self.name())?;
+        match self {
+            AnyEvent::AcpiDeviceOst { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::VserportChange { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::GuestPanicked { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::GuestCrashloaded { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::Shutdown { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::Reset { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::MigrationPass { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::PrManagerStatusChanged { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::UnplugPrimary { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::FailoverNegotiated { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::QuorumFailure { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::QuorumReportBad { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::MemoryDeviceSizeChange { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::DeviceUnplugGuestError { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::RtcChange { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::JobStatusChange { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::SpiceConnected { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::SpiceInitialized { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::SpiceDisconnected { data, .. } => state.serialize_field("data", data)?,
+            AnyEvent::Other {
+                data: Some(data), ..
+            } => state.serialize_field("data", data)?,
+            AnyEvent::Powerdown { .. }
+            | AnyEvent::Stop { .. }
+            | AnyEvent::Resume { .. }
+            | AnyEvent::SpiceMigrateCompleted { .. }
+            | AnyEvent::Other { data: None, .. } => state.skip_field("data")?,
+        }
+        state.serialize_field("timestamp", self.timestamp())?;
+        state.end()
+    }
+}
+
+/// Collapses bursts of similar events.
+///
+/// Consecutive events of the same name occurring within one second of the
+/// first one are collapsed, keeping only the last, much like the server does
+/// for rate-limited events. Events are therefore handed back with a delay:
+/// an event is returned by `push` once a different or later event arrives,
+/// and the last one can be retrieved with `flush`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventDeduplicator {
+    // The last event of the current burst, and the time its first event occurred.
+    pending: Option<(AnyEvent, u64)>,
+}
+
+impl EventDeduplicator {
+    const WINDOW_MICROSECONDS: u64 = 1_000_000;
+
+    /// Create a deduplicator with no pending event.
+    #[inline]
+    pub const fn new() -> Self {
+        EventDeduplicator { pending: None }
+    }
+
+    /// Feed an event, returning the previous one if it is not collapsed into this one.
+    pub fn push(&mut self, event: AnyEvent) -> Option<AnyEvent> {
+        let time = microseconds(event.timestamp());
+        match self.pending.take() {
+            Some((last, start))
+                if last.name() == event.name()
+                    && time.saturating_sub(start) < Self::WINDOW_MICROSECONDS =>
+            {
+                self.pending = Some((event, start));
+                None
+            }
+            previous => {
+                self.pending = Some((event, time));
+                previous.map(|(last, _)| last)
+            }
+        }
+    }
+
+    /// Take the pending event, if any.
+    #[inline]
+    pub fn flush(&mut self) -> Option<AnyEvent> {
+        self.pending.take().map(|(last, _)| last)
+    }
+}
+
+/// Tracks the state of background jobs.
+///
+/// Jobs are learnt from 'query-jobs' snapshots, and their status is kept up
+/// to date from 'JOB_STATUS_CHANGE' events in between. The map is ordered by
+/// job id, since `HashMap` is not available without `std`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JobTracker {
+    jobs: BTreeMap<String, JobInfo>,
+}
+
+impl JobTracker {
+    /// Create a tracker with no known job.
+    #[inline]
+    pub const fn new() -> Self {
+        JobTracker {
+            jobs: BTreeMap::new(),
+        }
+    }
+
+    /// Replace the known jobs with the result of 'query-jobs'.
+    pub fn update(&mut self, jobs: Vec<JobInfo>) {
+        self.jobs = jobs.into_iter().map(|job| (job.id.clone(), job)).collect();
+    }
+
+    /// Apply a status change of a job.
+    ///
+    /// Jobs not seen in a snapshot yet are ignored, and jobs reaching the
+    /// 'null' status are forgotten.
+    pub fn apply(&mut self, event: &JobStatusChangeEvent) {
+        if event.status == JobStatus::Null {
+            self.jobs.remove(&event.id);
+        } else if let Some(job) = self.jobs.get_mut(&event.id) {
+            job.status = event.status;
+        }
+    }
+
+    /// Feed any event, applying it if it is a 'JOB_STATUS_CHANGE'.
+    #[inline]
+    pub fn ingest(&mut self, event: &AnyEvent) {
+        if let AnyEvent::JobStatusChange { data, .. } = event {
+            self.apply(data);
+        }
+    }
+
+    /// Information about the job of this id, if known.
+    #[inline]
+    pub fn get(&self, id: &str) -> Option<&JobInfo> {
+        self.jobs.get(id)
+    }
+
+    /// Current status of the job of this id, if known.
+    #[inline]
+    pub fn status(&self, id: &str) -> Option<JobStatus> {
+        self.jobs.get(id).map(|job| job.status)
+    }
+}
+
+fn microseconds(timestamp: &Timestamp) -> u64 {
+    timestamp
+        .seconds
+        .saturating_mul(1_000_000)
+        .saturating_add(timestamp.microseconds)
+}
+
+// A missing `data` member is treated as an empty json-object.
+fn decode_data<T: DeserializeOwned>(data: Option<Value>) -> Result<T, serde_json::Error> {
+    serde_json::from_value(data.unwrap_or_else(|| Value::Object(Map::new())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnyEvent, EventDeduplicator, JobTracker};
+    use crate::acpi::{AcpiDeviceOstEvent, AcpiOstInfo, AcpiSlotType};
+    use crate::block::{
+        PrManagerStatusChangedEvent, QuorumFailureEvent, QuorumOpType, QuorumReportBadEvent,
+    };
+    use crate::char_dev::VserportChangeEvent;
+    use crate::generic::Timestamp;
+    use crate::job::{JobInfo, JobStatus, JobStatusChangeEvent, JobType};
+    use crate::machine::MemoryDeviceSizeChangeEvent;
+    use crate::migration::{MigrationPassEvent, UnplugPrimaryEvent};
+    use crate::misc::RtcChangeEvent;
+    use crate::net::FailoverNegotiatedEvent;
+    use crate::qdev::DeviceUnplugGuestErrorEvent;
+    use crate::vm_run_state::{
+        GuestCrashloadedEvent, GuestPanicAction, GuestPanicInformation,
+        GuestPanicInformationHyperV, ResetEvent, ShutdownCause, ShutdownEvent,
+    };
+    use alloc::{
+        string::{String, ToString},
+        vec,
+    };
+    use serde_json::Value;
+
+    #[test]
+    fn acpi_device_ost_event() {
+        let string = r#"{
+            "event": "ACPI_DEVICE_OST",
+            "data": {"info": {"device": "d1", "slot": "0", "slot-type": "DIMM", "source": 1, "status": 0}},
+            "timestamp": {"seconds": 1265044230, "microseconds": 450486}
+        }"#;
+        let value = AnyEvent::AcpiDeviceOst {
+            data: AcpiDeviceOstEvent {
+                info: AcpiOstInfo {
+                    device: Some("d1".to_string()),
+                    slot: "0".to_string(),
+                    slot_type: AcpiSlotType::Dimm,
+                    source: 1,
+                    status: 0,
+                },
+            },
+            timestamp: Timestamp {
+                seconds: 1265044230,
+                microseconds: 450486,
+            },
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn vserport_change_event() {
+        let string = r#"{
+            "event": "VSERPORT_CHANGE",
+            "data": {"id": "channel0", "open": true},
+            "timestamp": {"seconds": 1401385907, "microseconds": 422329}
+        }"#;
+        let value = AnyEvent::VserportChange {
+            data: VserportChangeEvent {
+                id: "channel0".to_string(),
+                open: true,
+            },
+            timestamp: Timestamp {
+                seconds: 1401385907,
+                microseconds: 422329,
+            },
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn guest_crashloaded_event() {
+        let string = r#"{
+            "event": "GUEST_CRASHLOADED",
+            "data": {
+                "action": "run",
+                "info": {"type": "hyper-v", "arg1": 1, "arg2": 2, "arg3": 3, "arg4": 4, "arg5": 5}
+            },
+            "timestamp": {"seconds": 1648245259, "microseconds": 893771}
+        }"#;
+        let value = AnyEvent::GuestCrashloaded {
+            data: GuestCrashloadedEvent {
+                action: GuestPanicAction::Run,
+                info: Some(GuestPanicInformation::HyperV(GuestPanicInformationHyperV {
+                    arg1: 1,
+                    arg2: 2,
+                    arg3: 3,
+                    arg4: 4,
+                    arg5: 5,
+                })),
+            },
+            timestamp: Timestamp {
+                seconds: 1648245259,
+                microseconds: 893771,
+            },
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn shutdown_event_is_terminal() {
+        let string = r#"{
+            "event": "SHUTDOWN",
+            "data": {"guest": true, "reason": "guest-shutdown"},
+            "timestamp": {"seconds": 1267040730, "microseconds": 682951}
+        }"#;
+        let value = AnyEvent::Shutdown {
+            data: ShutdownEvent {
+                guest: true,
+                reason: ShutdownCause::GuestShutdown,
+            },
+            timestamp: Timestamp {
+                seconds: 1267040730,
+                microseconds: 682951,
+            },
+        };
+        let event: AnyEvent = serde_json::from_str(string).unwrap();
+        assert_eq!(value, event);
+        assert_eq!(event.name(), "SHUTDOWN");
+        assert!(event.is_terminal());
+    }
+
+    #[test]
+    fn resume_event_is_not_terminal() {
+        let string = r#"{
+            "event": "RESUME",
+            "timestamp": {"seconds": 1271770767, "microseconds": 582542}
+        }"#;
+        let value = AnyEvent::Resume {
+            timestamp: Timestamp {
+                seconds: 1271770767,
+                microseconds: 582542,
+            },
+        };
+        let event: AnyEvent = serde_json::from_str(string).unwrap();
+        assert_eq!(value, event);
+        assert_eq!(event.name(), "RESUME");
+        assert!(!event.is_terminal());
+    }
+
+    #[test]
+    fn reset_event_name() {
+        let string = r#"{
+            "event": "RESET",
+            "data": {"guest": false, "reason": "host-qmp-system-reset"},
+            "timestamp": {"seconds": 1267041653, "microseconds": 9518}
+        }"#;
+        let value = AnyEvent::Reset {
+            data: ResetEvent {
+                guest: false,
+                reason: ShutdownCause::HostQmpSystemReset,
+            },
+            timestamp: Timestamp {
+                seconds: 1267041653,
+                microseconds: 9518,
+            },
+        };
+        let event: AnyEvent = serde_json::from_str(string).unwrap();
+        assert_eq!(value, event);
+        assert_eq!(event.name(), "RESET");
+        assert!(!event.is_terminal());
+    }
+
+    #[test]
+    fn unknown_event() {
+        let string = r#"{
+            "event": "X_UNKNOWN_EVENT",
+            "data": {"answer": 42},
+            "timestamp": {"seconds": 1265044230, "microseconds": 450486}
+        }"#;
+        let value = AnyEvent::Other {
+            event: "X_UNKNOWN_EVENT".to_string(),
+            data: Some(serde_json::json!({"answer": 42})),
+            timestamp: Timestamp {
+                seconds: 1265044230,
+                microseconds: 450486,
+            },
+        };
+        let event: AnyEvent = serde_json::from_str(string).unwrap();
+        assert_eq!(value, event);
+        assert_eq!(event.name(), "X_UNKNOWN_EVENT");
+    }
+
+    #[test]
+    fn migration_pass_event() {
+        let string = r#"{
+            "event": "MIGRATION_PASS",
+            "data": {"pass": 2},
+            "timestamp": {"seconds": 1449669631, "microseconds": 239234}
+        }"#;
+        let value = AnyEvent::MigrationPass {
+            data: MigrationPassEvent { pass: 2 },
+            timestamp: Timestamp {
+                seconds: 1449669631,
+                microseconds: 239234,
+            },
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn pr_manager_status_changed_event() {
+        let string = r#"{
+            "event": "PR_MANAGER_STATUS_CHANGED",
+            "data": {"id": "pr-helper0", "connected": true},
+            "timestamp": {"seconds": 1519995005, "microseconds": 683825}
+        }"#;
+        let value = AnyEvent::PrManagerStatusChanged {
+            data: PrManagerStatusChangedEvent {
+                id: "pr-helper0".to_string(),
+                connected: true,
+            },
+            timestamp: Timestamp {
+                seconds: 1519995005,
+                microseconds: 683825,
+            },
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn unplug_primary_event() {
+        let string = r#"{
+            "event": "UNPLUG_PRIMARY",
+            "data": {"device-id": "hostdev0"},
+            "timestamp": {"seconds": 1265044230, "microseconds": 450486}
+        }"#;
+        let value = AnyEvent::UnplugPrimary {
+            data: UnplugPrimaryEvent {
+                device_id: "hostdev0".to_string(),
+            },
+            timestamp: Timestamp {
+                seconds: 1265044230,
+                microseconds: 450486,
+            },
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn failover_negotiated_event() {
+        let string = r#"{
+            "event": "FAILOVER_NEGOTIATED",
+            "data": {"device-id": "net1"},
+            "timestamp": {"seconds": 1368697518, "microseconds": 326866}
+        }"#;
+        let value = AnyEvent::FailoverNegotiated {
+            data: FailoverNegotiatedEvent {
+                device_id: "net1".to_string(),
+            },
+            timestamp: Timestamp {
+                seconds: 1368697518,
+                microseconds: 326866,
+            },
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn quorum_failure_event() {
+        let string = r#"{
+            "event": "QUORUM_FAILURE",
+            "data": {"reference": "usr1", "sector-num": 345435, "sectors-count": 5},
+            "timestamp": {"seconds": 1344522075, "microseconds": 745528}
+        }"#;
+        let value = AnyEvent::QuorumFailure {
+            data: QuorumFailureEvent {
+                reference: Some("usr1".to_string()),
+                sector_num: 345435,
+                sectors_count: 5,
+            },
+            timestamp: Timestamp {
+                seconds: 1344522075,
+                microseconds: 745528,
+            },
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn quorum_report_bad_event() {
+        let string = r#"{
+            "event": "QUORUM_REPORT_BAD",
+            "data": {"node-name": "node0", "sector-num": 345435, "sectors-count": 5, "type": "read"},
+            "timestamp": {"seconds": 1344522075, "microseconds": 745528}
+        }"#;
+        let value = AnyEvent::QuorumReportBad {
+            data: QuorumReportBadEvent {
+                op_type: QuorumOpType::Read,
+                error: None,
+                node_name: "node0".to_string(),
+                sector_num: 345435,
+                sectors_count: 5,
+            },
+            timestamp: Timestamp {
+                seconds: 1344522075,
+                microseconds: 745528,
+            },
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn memory_device_size_change_event() {
+        let string = r#"{
+            "event": "MEMORY_DEVICE_SIZE_CHANGE",
+            "data": {"id": "vm0", "size": 1073741824, "qom-path": "/machine/unattached/device[2]"},
+            "timestamp": {"seconds": 1588168529, "microseconds": 201316}
+        }"#;
+        let value = AnyEvent::MemoryDeviceSizeChange {
+            data: MemoryDeviceSizeChangeEvent {
+                id: Some("vm0".to_string()),
+                size: 1073741824,
+                qom_path: "/machine/unattached/device[2]".to_string(),
+            },
+            timestamp: Timestamp {
+                seconds: 1588168529,
+                microseconds: 201316,
+            },
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn device_unplug_guest_error_event() {
+        let string = r#"{
+            "event": "DEVICE_UNPLUG_GUEST_ERROR",
+            "data": {"path": "/machine/peripheral/core1"},
+            "timestamp": {"seconds": 1615570772, "microseconds": 202844}
+        }"#;
+        let value = AnyEvent::DeviceUnplugGuestError {
+            data: DeviceUnplugGuestErrorEvent {
+                device: None,
+                path: "/machine/peripheral/core1".to_string(),
+            },
+            timestamp: Timestamp {
+                seconds: 1615570772,
+                microseconds: 202844,
+            },
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn rtc_change_event() {
+        let string = r#"{
+            "event": "RTC_CHANGE",
+            "data": {"offset": 78, "qom-path": "/machine/unattached/device[0]"},
+            "timestamp": {"seconds": 1267020223, "microseconds": 435656}
+        }"#;
+        let value = AnyEvent::RtcChange {
+            data: RtcChangeEvent {
+                offset: 78,
+                qom_path: "/machine/unattached/device[0]".to_string(),
+            },
+            timestamp: Timestamp {
+                seconds: 1267020223,
+                microseconds: 435656,
+            },
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn event_deduplicator_rtc_change() {
+        let rtc_change = |offset, seconds, microseconds| AnyEvent::RtcChange {
+            data: RtcChangeEvent {
+                offset,
+                qom_path: "/machine/unattached/device[0]".to_string(),
+            },
+            timestamp: Timestamp {
+                seconds,
+                microseconds,
+            },
+        };
+        let mut dedup = EventDeduplicator::new();
+        assert_eq!(dedup.push(rtc_change(1, 1267020223, 100000)), None);
+        assert_eq!(dedup.push(rtc_change(2, 1267020223, 600000)), None);
+        assert_eq!(dedup.push(rtc_change(3, 1267020224, 50000)), None);
+        let stop = AnyEvent::Stop {
+            timestamp: Timestamp {
+                seconds: 1267020224,
+                microseconds: 70000,
+            },
+        };
+        assert_eq!(
+            dedup.push(stop.clone()),
+            Some(rtc_change(3, 1267020224, 50000))
+        );
+        assert_eq!(dedup.flush(), Some(stop));
+        assert_eq!(dedup.flush(), None);
+    }
+
+    #[test]
+    fn data_as_custom_event() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        #[serde(rename_all = "kebab-case")]
+        struct VfuClientHangup {
+            vfu_id: String,
+            dev_id: String,
+        }
+        let string = r#"{
+            "event": "VFU_CLIENT_HANGUP",
+            "data": {"vfu-id": "vfu1", "vfu-qom-path": "/objects/vfu1",
+                     "dev-id": "sas1", "dev-qom-path": "/machine/peripheral/sas1"},
+            "timestamp": {"seconds": 1265044230, "microseconds": 450486}
+        }"#;
+        let event: AnyEvent = serde_json::from_str(string).unwrap();
+        let data: VfuClientHangup = event.data_as().unwrap().unwrap();
+        assert_eq!(
+            data,
+            VfuClientHangup {
+                vfu_id: "vfu1".to_string(),
+                dev_id: "sas1".to_string(),
+            }
+        );
+        assert!(event.data_as::<u64>().unwrap().is_err());
+
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Offset {
+            offset: i64,
+        }
+        let event = AnyEvent::RtcChange {
+            data: RtcChangeEvent {
+                offset: 78,
+                qom_path: "/machine/unattached/device[0]".to_string(),
+            },
+            timestamp: Timestamp {
+                seconds: 1267020223,
+                microseconds: 435656,
+            },
+        };
+        let offset: Offset = event.data_as().unwrap().unwrap();
+        assert_eq!(offset, Offset { offset: 78 });
+        let stop = AnyEvent::Stop {
+            timestamp: Timestamp {
+                seconds: 1267020223,
+                microseconds: 435656,
+            },
+        };
+        assert!(stop.data_as::<Offset>().is_none());
+    }
+
+    #[test]
+    fn job_status_change_event() {
+        let string = r#"{
+            "event": "JOB_STATUS_CHANGE",
+            "data": {"id": "backup0", "status": "concluded"},
+            "timestamp": {"seconds": 1267040730, "microseconds": 682951}
+        }"#;
+        let value = AnyEvent::JobStatusChange {
+            data: JobStatusChangeEvent {
+                id: "backup0".to_string(),
+                status: JobStatus::Concluded,
+            },
+            timestamp: Timestamp {
+                seconds: 1267040730,
+                microseconds: 682951,
+            },
+        };
+        let event: AnyEvent = serde_json::from_str(string).unwrap();
+        assert_eq!(value, event);
+        assert_eq!(event.name(), "JOB_STATUS_CHANGE");
+    }
+
+    #[test]
+    fn job_tracker_status_change() {
+        let job = |id: &str, status| JobInfo {
+            id: id.to_string(),
+            job_type: JobType::Backup,
+            status,
+            current_progress: 0,
+            total_progress: 1024,
+            error: None,
+        };
+        let mut tracker = JobTracker::new();
+        tracker.update(vec![
+            job("backup0", JobStatus::Running),
+            job("backup1", JobStatus::Running),
+        ]);
+        let change = |id: &str, status| AnyEvent::JobStatusChange {
+            data: JobStatusChangeEvent {
+                id: id.to_string(),
+                status,
+            },
+            timestamp: Timestamp {
+                seconds: 1267040730,
+                microseconds: 682951,
+            },
+        };
+        tracker.ingest(&change("backup0", JobStatus::Paused));
+        tracker.ingest(&change("backup1", JobStatus::Null));
+        tracker.ingest(&change("unknown", JobStatus::Running));
+        assert_eq!(tracker.status("backup0"), Some(JobStatus::Paused));
+        assert_eq!(tracker.get("backup0").unwrap().total_progress, 1024);
+        assert_eq!(tracker.status("backup1"), None);
+        assert_eq!(tracker.status("unknown"), None);
+    }
+
+    #[test]
+    fn any_event_round_trip() {
+        let captured = [
+            r#"{"event": "ACPI_DEVICE_OST", "data": {"info": {"device": "d1", "slot": "0", "slot-type": "DIMM", "source": 1, "status": 0}}, "timestamp": {"seconds": 1265044230, "microseconds": 450486}}"#,
+            r#"{"event": "VSERPORT_CHANGE", "data": {"id": "channel0", "open": true}, "timestamp": {"seconds": 1401385907, "microseconds": 422329}}"#,
+            r#"{"event": "GUEST_PANICKED", "data": {"action": "pause"}, "timestamp": {"seconds": 1648245231, "microseconds": 900001}}"#,
+            r#"{"event": "GUEST_CRASHLOADED", "data": {"action": "run", "info": {"type": "hyper-v", "arg1": 1, "arg2": 2, "arg3": 3, "arg4": 4, "arg5": 5}}, "timestamp": {"seconds": 1648245259, "microseconds": 893771}}"#,
+            r#"{"event": "SHUTDOWN", "data": {"guest": true, "reason": "guest-shutdown"}, "timestamp": {"seconds": 1267040730, "microseconds": 682951}}"#,
+            r#"{"event": "POWERDOWN", "timestamp": {"seconds": 1267040730, "microseconds": 682951}}"#,
+            r#"{"event": "RESET", "data": {"guest": false, "reason": "host-qmp-system-reset"}, "timestamp": {"seconds": 1267041653, "microseconds": 9518}}"#,
+            r#"{"event": "STOP", "timestamp": {"seconds": 1267041730, "microseconds": 281295}}"#,
+            r#"{"event": "RESUME", "timestamp": {"seconds": 1271770767, "microseconds": 582542}}"#,
+            r#"{"event": "MIGRATION_PASS", "data": {"pass": 2}, "timestamp": {"seconds": 1449669631, "microseconds": 239234}}"#,
+            r#"{"event": "PR_MANAGER_STATUS_CHANGED", "data": {"id": "pr-helper0", "connected": true}, "timestamp": {"seconds": 1519995005, "microseconds": 683825}}"#,
+            r#"{"event": "UNPLUG_PRIMARY", "data": {"device-id": "hostdev0"}, "timestamp": {"seconds": 1265044230, "microseconds": 450486}}"#,
+            r#"{"event": "FAILOVER_NEGOTIATED", "data": {"device-id": "net1"}, "timestamp": {"seconds": 1368697518, "microseconds": 326866}}"#,
+            r#"{"event": "QUORUM_FAILURE", "data": {"reference": "usr1", "sector-num": 345435, "sectors-count": 5}, "timestamp": {"seconds": 1344522075, "microseconds": 745528}}"#,
+            r#"{"event": "QUORUM_REPORT_BAD", "data": {"node-name": "node0", "sector-num": 345435, "sectors-count": 5, "type": "read"}, "timestamp": {"seconds": 1344522075, "microseconds": 745528}}"#,
+            r#"{"event": "MEMORY_DEVICE_SIZE_CHANGE", "data": {"id": "vm0", "size": 1073741824, "qom-path": "/machine/unattached/device[2]"}, "timestamp": {"seconds": 1588168529, "microseconds": 201316}}"#,
+            r#"{"event": "DEVICE_UNPLUG_GUEST_ERROR", "data": {"path": "/machine/peripheral/core1"}, "timestamp": {"seconds": 1615570772, "microseconds": 202844}}"#,
+            r#"{"event": "RTC_CHANGE", "data": {"offset": 78, "qom-path": "/machine/unattached/device[0]"}, "timestamp": {"seconds": 1267020223, "microseconds": 435656}}"#,
+            r#"{"event": "JOB_STATUS_CHANGE", "data": {"id": "backup0", "status": "concluded"}, "timestamp": {"seconds": 1267040730, "microseconds": 682951}}"#,
+            r#"{"event": "SPICE_CONNECTED", "data": {"server": {"port": "5920", "family": "ipv4", "host": "127.0.0.1"}, "client": {"port": "52873", "family": "ipv4", "host": "127.0.0.1"}}, "timestamp": {"seconds": 1290688046, "microseconds": 388707}}"#,
+            r#"{"event": "SPICE_INITIALIZED", "data": {"server": {"auth": "spice", "port": "5921", "family": "ipv4", "host": "127.0.0.1"}, "client": {"port": "49004", "family": "ipv4", "channel-type": 3, "connection-id": 1804289383, "host": "127.0.0.1", "channel-id": 0, "tls": true}}, "timestamp": {"seconds": 1290688046, "microseconds": 417172}}"#,
+            r#"{"event": "SPICE_DISCONNECTED", "data": {"server": {"port": "5920", "family": "ipv4", "host": "127.0.0.1"}, "client": {"port": "52873", "family": "ipv4", "host": "127.0.0.1"}}, "timestamp": {"seconds": 1290688046, "microseconds": 388707}}"#,
+            r#"{"event": "SPICE_MIGRATE_COMPLETED", "timestamp": {"seconds": 1290688046, "microseconds": 417172}}"#,
+            r#"{"event": "X_UNKNOWN_EVENT", "data": {"answer": 42}, "timestamp": {"seconds": 1265044230, "microseconds": 450486}}"#,
+            r#"{"event": "X_UNKNOWN_NO_DATA", "timestamp": {"seconds": 1265044230, "microseconds": 450486}}"#,
+        ];
+        for string in captured {
+            let wire: Value = serde_json::from_str(string).unwrap();
+            let event: AnyEvent = serde_json::from_str(string).unwrap();
+            if !event.name().starts_with("X_") {
+                assert!(!matches!(event, AnyEvent::Other { .. }), "{string}");
+            }
+            assert_eq!(wire, serde_json::to_value(&event).unwrap(), "{string}");
+            let replayed: AnyEvent = serde_json::from_value(wire).unwrap();
+            assert_eq!(event, replayed);
+        }
+    }
+}
@@ -1,6 +1,13 @@
 //! Protocol generic specification.
-use crate::qmp_monitor::VersionInfo;
-use serde::{Deserialize, Serialize};
+use crate::qmp_monitor::{QmpCapability, VersionInfo};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use core::marker::PhantomData;
+#[cfg(feature = "json")]
+use core::str::FromStr;
+use serde::{Deserialize, Deserializer, Serialize, de, de::DeserializeOwned};
+#[cfg(feature = "json")]
+use serde_json::Value;
 
 /// Server greeting structure.
 ///
@@ -9,14 +16,38 @@ use serde::{Deserialize, Serialize};
 /// established and that the Server is ready for capabilities
 /// negotiation.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct ServerGreeting {
     /// Information on a greeting QMP server.
     #[serde(rename = "QMP")]
     pub qmp: QmpServerGreeting,
 }
 
+impl ServerGreeting {
+    /// Capabilities advertised by the server that this library recognizes.
+    ///
+    /// Unknown capability names are ignored.
+    pub fn typed_capabilities(&self) -> Vec<QmpCapability> {
+        self.qmp
+            .capabilities
+            .iter()
+            .filter_map(|name| match name.as_str() {
+                "oob" => Some(QmpCapability::Oob),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns whether the server advertised the given capability.
+    #[inline]
+    pub fn supports(&self, cap: QmpCapability) -> bool {
+        self.typed_capabilities().contains(&cap)
+    }
+}
+
 /// A greeting QMP server.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct QmpServerGreeting {
     /// Server's version information.
     ///
@@ -51,6 +82,77 @@ pub struct Command<T, U> {
     pub id: Option<U>,
 }
 
+/// An in-band command using a json-number as its transaction id.
+pub type SimpleCommand<T> = Command<T, u64>;
+
+/// A source of monotonically increasing transaction ids.
+///
+/// Ids start at 0 and are suitable for a `SimpleCommand`. The generator is an
+/// endless iterator: `next()` always returns a fresh id, distinct from and
+/// greater than all the previous ones.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IdGenerator {
+    next: u64,
+}
+
+impl IdGenerator {
+    /// Create a generator yielding ids from 0.
+    #[inline]
+    pub const fn new() -> Self {
+        IdGenerator { next: 0 }
+    }
+}
+
+impl Iterator for IdGenerator {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        let id = self.next;
+        self.next += 1;
+        Some(id)
+    }
+}
+
+impl<T, U> Command<T, U> {
+    /// Attach a transaction id to this command, replacing any existing one.
+    ///
+    /// The id can be of any type serializing into a json-value, for example
+    /// a `u64` incremented for each successive command, or a string.
+    #[inline]
+    pub fn with_id<V>(self, id: V) -> Command<T, V> {
+        Command {
+            execute: self.execute,
+            arguments: self.arguments,
+            id: Some(id),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl Command<Value, ()> {
+    /// Build a command not modeled by this library from its name and raw arguments.
+    ///
+    /// This is an escape hatch for experimental or downstream-specific commands.
+    #[inline]
+    pub fn raw(execute: impl Into<String>, arguments: Option<Value>) -> Self {
+        Command {
+            execute: execute.into(),
+            arguments,
+            id: None,
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T: Serialize, U: Serialize> Command<T, U> {
+    /// Render this command as indented multi-line JSON, for debugging and logging.
+    #[inline]
+    pub fn to_pretty_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
 /// An out-of-band command.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct OobCommand<T, U> {
@@ -75,6 +177,93 @@ pub struct OobCommand<T, U> {
     pub id: Option<U>,
 }
 
+impl<T, U> OobCommand<T, U> {
+    /// Convert an in-band command into its out-of-band form.
+    ///
+    /// The command name is moved into `exec-oob`, while arguments and id are preserved.
+    #[inline]
+    pub fn from_command(cmd: Command<T, U>) -> Self {
+        OobCommand {
+            exec_oob: cmd.execute,
+            arguments: cmd.arguments,
+            id: cmd.id,
+        }
+    }
+}
+
+impl<T: OobSafe, U> OobCommand<T, U> {
+    /// Convert an in-band command into its out-of-band form, for a command
+    /// whose arguments structure is permitted out-of-band.
+    ///
+    /// The command is given back if its name is not the one of `T`.
+    #[inline]
+    pub fn try_from_command(cmd: Command<T, U>) -> Result<Self, Command<T, U>> {
+        if cmd.execute != T::NAME {
+            return Err(cmd);
+        }
+        Ok(OobCommand::from_command(cmd))
+    }
+}
+
+impl<U> OobCommand<(), U> {
+    /// Out-of-band form of the command without arguments marked by `C`.
+    #[inline]
+    pub fn from_marker<C: OobSafe>(id: Option<U>) -> Self {
+        OobCommand {
+            exec_oob: String::from(C::NAME),
+            arguments: None,
+            id,
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T: Serialize, U: Serialize> OobCommand<T, U> {
+    /// Render this command as indented multi-line JSON, for debugging and logging.
+    #[inline]
+    pub fn to_pretty_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Either an in-band or an out-of-band command.
+///
+/// When deserializing, the variant is picked by the presence of `execute`
+/// or `exec-oob`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AnyCommand<T, U> {
+    /// An in-band command.
+    InBand(Command<T, U>),
+    /// An out-of-band command.
+    OutOfBand(OobCommand<T, U>),
+}
+
+/// A QMP command with a known name and return type.
+///
+/// It is implemented on the arguments structure of each command, or on a marker
+/// structure for commands taking no arguments, so that the type of the `return`
+/// member can be inferred from the command being executed.
+pub trait QmpCommand {
+    /// Type of the data returned by the command on success.
+    type Return: DeserializeOwned;
+    /// Name of the command, as used in the `execute` member.
+    const NAME: &'static str;
+}
+
+/// A QMP command permitted to be executed out-of-band.
+///
+/// Out-of-band commands must not take the big QEMU lock, so only commands
+/// declared with `allow-oob` in the QEMU schema qualify. Of the commands
+/// modeled by this library, these are 'migrate-recover', 'migrate-pause'
+/// and 'yank'.
+pub trait OobSafe: QmpCommand {}
+
+/// An empty json-object returned by commands that do not return data.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Empty {}
+
 /// A response to a command.
 ///
 /// There are two possible responses which the Server will issue as
@@ -96,7 +285,11 @@ pub struct Response<T, U> {
 }
 
 /// Either this response succeeds or fails with an error.
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// When deserializing, the variant is picked by the presence of the `return`
+/// or `error` member, so that a permissive `T` such as `serde_json::Value`
+/// never captures an error response.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
 #[serde(untagged)]
 pub enum ReturnOrError<T> {
     /// The response succeeded.
@@ -112,15 +305,112 @@ pub enum ReturnOrError<T> {
     },
     /// The response failed with an error.
     Error {
-        /// The `class` member contains the error class name (eg. "GenericError").
+        /// The `error` member contains the error class and its description.
         #[serde(rename = "error")]
-        class: String,
-        /// The `desc` member is a human-readable error message. Clients should not attempt to parse this message.
-        #[serde(rename = "desc")]
-        description: String,
+        error: ErrorInfo,
     },
 }
 
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ReturnOrError<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ReturnOrErrorVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> de::Visitor<'de> for ReturnOrErrorVisitor<T> {
+            type Value = ReturnOrError<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a json-object with either a `return` or an `error` member")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut response = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    let value = match key.as_str() {
+                        "return" => ReturnOrError::Return {
+                            value: map.next_value()?,
+                        },
+                        "error" => ReturnOrError::Error {
+                            error: map.next_value()?,
+                        },
+                        _ => {
+                            map.next_value::<de::IgnoredAny>()?;
+                            continue;
+                        }
+                    };
+                    if response.replace(value).is_some() {
+                        return Err(de::Error::custom(
+                            "expected only one of the `return` and `error` members",
+                        ));
+                    }
+                }
+                response.ok_or_else(|| de::Error::custom("missing `return` or `error` member"))
+            }
+        }
+
+        deserializer.deserialize_map(ReturnOrErrorVisitor(PhantomData))
+    }
+}
+
+/// Error information of a failed command.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ErrorInfo {
+    /// The `class` member contains the error class name (eg. "GenericError").
+    pub class: String,
+    /// The `desc` member is a human-readable error message. Clients should not attempt to parse this message.
+    #[serde(rename = "desc")]
+    pub description: String,
+}
+
+/// Error of decoding the response of a command.
+#[cfg(feature = "json")]
+#[derive(Debug)]
+pub enum QmpError {
+    /// The server failed to execute the command.
+    Server(ErrorInfo),
+    /// The response does not match the structure expected for the command.
+    Decode(serde_json::Error),
+}
+
+#[cfg(feature = "json")]
+impl fmt::Display for QmpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QmpError::Server(info) => write!(f, "{}: {}", info.class, info.description),
+            QmpError::Decode(err) => write!(f, "invalid response: {err}"),
+        }
+    }
+}
+
+#[cfg(all(feature = "std", feature = "json"))]
+impl std::error::Error for QmpError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            QmpError::Server(_) => None,
+            QmpError::Decode(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for QmpError {
+    #[inline]
+    fn from(err: serde_json::Error) -> Self {
+        QmpError::Decode(err)
+    }
+}
+
+/// Extract the data returned by command `C` from its response.
+///
+/// The response id, if any, is ignored.
+#[cfg(feature = "json")]
+pub fn parse_response<C: QmpCommand>(value: &Value) -> Result<C::Return, QmpError> {
+    let response = Response::<C::Return, Value>::deserialize(value)?;
+    match response.response {
+        ReturnOrError::Return { value } => Ok(value),
+        ReturnOrError::Error { error } => Err(QmpError::Server(error)),
+    }
+}
+
 /// Asynchronous events.
 ///
 /// As a result of state changes, the Server may send messages unilaterally
@@ -147,6 +437,7 @@ pub struct Event<T> {
 /// all but the last one are dropped, and the last one is delayed.
 /// "Similar" normally means same event type.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Timestamp {
     /// Number of seconds from the Unix Epoch.
     pub seconds: u64,
@@ -154,12 +445,101 @@ pub struct Timestamp {
     pub microseconds: u64,
 }
 
+#[cfg(feature = "chrono")]
+impl Timestamp {
+    /// Convert this timestamp into a UTC date and time.
+    ///
+    /// Timestamps too far in the future to be represented saturate to
+    /// `DateTime::<Utc>::MAX_UTC`.
+    pub fn to_datetime_utc(&self) -> chrono::DateTime<chrono::Utc> {
+        i64::try_from(self.seconds)
+            .ok()
+            .and_then(|seconds| {
+                let nanoseconds = u32::try_from(self.microseconds.checked_mul(1000)?).ok()?;
+                chrono::DateTime::from_timestamp(seconds, nanoseconds)
+            })
+            .unwrap_or(chrono::DateTime::<chrono::Utc>::MAX_UTC)
+    }
+
+    /// Create a timestamp from a UTC date and time, truncated to microseconds.
+    ///
+    /// Dates before the Unix Epoch are clamped to the Epoch.
+    pub fn from_datetime(datetime: &chrono::DateTime<chrono::Utc>) -> Self {
+        match u64::try_from(datetime.timestamp()) {
+            Ok(seconds) => Timestamp {
+                seconds,
+                microseconds: u64::from(datetime.timestamp_subsec_micros()),
+            },
+            Err(_) => Timestamp {
+                seconds: 0,
+                microseconds: 0,
+            },
+        }
+    }
+}
+
+/// Any message sent by the Server.
+///
+/// The variant is picked by the presence of the `QMP`, `return`/`error`
+/// or `event` member, so that a message of unknown shape can be classified
+/// without knowing the command it answers.
+#[cfg(feature = "json")]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum AnyResponse {
+    /// The greeting issued right after the connection is established.
+    Greeting(ServerGreeting),
+    /// A success or error response to a command.
+    Response(Response<Value, Value>),
+    /// An asynchronous event.
+    Event(Event<Value>),
+}
+
+#[cfg(feature = "json")]
+impl<'de> Deserialize<'de> for AnyResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        let Some(object) = value.as_object() else {
+            return Err(de::Error::custom("expected a json-object"));
+        };
+        let message = if object.contains_key("QMP") {
+            ServerGreeting::deserialize(value).map(AnyResponse::Greeting)
+        } else if object.contains_key("return") || object.contains_key("error") {
+            Response::deserialize(value).map(AnyResponse::Response)
+        } else if object.contains_key("event") {
+            Event::deserialize(value).map(AnyResponse::Event)
+        } else {
+            return Err(de::Error::custom(
+                "expected a `QMP`, `return`, `error` or `event` member",
+            ));
+        };
+        message.map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "json")]
+impl FromStr for AnyResponse {
+    type Err = serde_json::Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
 // TODO tests for generic structures. Refer to 'QMP Examples' section.
 #[cfg(test)]
 mod tests {
-    use super::{Command, Response, ServerGreeting};
-    use crate::qmp_monitor::{VersionInfo, VersionTriple};
+    #[cfg(feature = "json")]
+    use super::AnyResponse;
+    use super::{
+        AnyCommand, Command, IdGenerator, OobCommand, Response, ReturnOrError, ServerGreeting,
+        SimpleCommand,
+    };
+    use crate::qmp_monitor::{QmpCapability, VersionInfo, VersionTriple};
+    use alloc::{string::ToString, vec, vec::Vec};
     use serde::{Deserialize, Serialize};
+    use serde_json::Value;
 
     // Test cases from: https://wiki.qemu.org/Documentation/QMP
     #[test]
@@ -207,6 +587,251 @@ mod tests {
         assert_eq!(s2_value, serde_json::from_str(s2_input).unwrap());
     }
 
+    // Greeting captured verbatim from a QEMU 7.2 server.
+    #[test]
+    fn qemu_7_greeting() {
+        let input = r#"{"QMP": {"version": {"qemu": {"micro": 0, "minor": 2, "major": 7}, "package": "Debian 1:7.2+dfsg-7+deb12u6"}, "capabilities": ["oob"]}}"#;
+        let value = ServerGreeting {
+            qmp: super::QmpServerGreeting {
+                version: VersionInfo {
+                    qemu: VersionTriple::new(7, 2, 0),
+                    package: "Debian 1:7.2+dfsg-7+deb12u6".to_string(),
+                },
+                capabilities: vec!["oob".to_string()],
+            },
+        };
+        assert_eq!(value, serde_json::from_str(input).unwrap());
+    }
+
+    #[test]
+    fn greeting_typed_capabilities() {
+        let oob_input = r#"{
+            "QMP": {
+                "version": {
+                    "qemu": {"micro": 0, "minor": 2, "major": 8},
+                    "package": ""
+                },
+                "capabilities": ["oob"]
+            }
+        }"#;
+        let greeting: ServerGreeting = serde_json::from_str(oob_input).unwrap();
+        assert_eq!(greeting.typed_capabilities(), vec![QmpCapability::Oob]);
+        assert!(greeting.supports(QmpCapability::Oob));
+
+        let unknown_input = r#"{
+            "QMP": {
+                "version": {
+                    "qemu": {"micro": 0, "minor": 2, "major": 8},
+                    "package": ""
+                },
+                "capabilities": ["x-unknown-capability"]
+            }
+        }"#;
+        let greeting: ServerGreeting = serde_json::from_str(unknown_input).unwrap();
+        assert!(greeting.typed_capabilities().is_empty());
+        assert!(!greeting.supports(QmpCapability::Oob));
+    }
+
+    #[test]
+    fn response_without_id() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct EmptyStruct {}
+        let value: Response<EmptyStruct, u64> = Response {
+            response: crate::generic::ReturnOrError::Return {
+                value: EmptyStruct {},
+            },
+            id: None,
+        };
+        assert_eq!(value, serde_json::from_str(r#"{"return":{}}"#).unwrap());
+        assert_eq!(r#"{"return":{}}"#, serde_json::to_string(&value).unwrap());
+    }
+
+    #[test]
+    fn command_with_id() {
+        let cmd: SimpleCommand<()> = crate::query_version().with_id(1);
+        let compact_string = r#"{"execute":"query-version","id":1}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let cmd = crate::query_version().with_id("version-request");
+        let compact_string = r#"{"execute":"query-version","id":"version-request"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn command_to_pretty_json() {
+        let pretty = crate::query_version().with_id(1).to_pretty_json().unwrap();
+        assert!(pretty.contains('\n'));
+        assert!(pretty.contains(r#""execute": "query-version""#));
+        let pretty = OobCommand::from_command(crate::query_version())
+            .to_pretty_json()
+            .unwrap();
+        assert!(pretty.contains('\n'));
+        assert!(pretty.contains(r#""exec-oob": "query-version""#));
+    }
+
+    #[test]
+    fn oob_command_from_command() {
+        let cmd: Command<(), u64> = Command {
+            execute: "query-version".to_string(),
+            arguments: None,
+            id: Some(42),
+        };
+        let oob = OobCommand::from_command(cmd);
+        let compact_string = r#"{"exec-oob":"query-version","id":42}"#;
+        assert_eq!(compact_string, serde_json::to_string(&oob).unwrap());
+        let oob = OobCommand::from_command(crate::query_version());
+        let compact_string = r#"{"exec-oob":"query-version"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&oob).unwrap());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn any_response_classify() {
+        let greeting = r#"{"QMP": {"version": {"qemu": {"micro": 0, "minor": 2, "major": 8}, "package": ""}, "capabilities": []}}"#;
+        let message: AnyResponse = greeting.parse().unwrap();
+        assert!(matches!(message, AnyResponse::Greeting(_)));
+
+        let success = r#"{"return": {}, "id": "a1"}"#;
+        let AnyResponse::Response(response) = success.parse().unwrap() else {
+            panic!("expected a response");
+        };
+        assert_eq!(response.id, Some(Value::from("a1")));
+        assert!(matches!(response.response, ReturnOrError::Return { .. }));
+
+        let error = r#"{"error": {"class": "CommandNotFound", "desc": "The command foo has not been found"}}"#;
+        let AnyResponse::Response(response) = error.parse().unwrap() else {
+            panic!("expected a response");
+        };
+        let ReturnOrError::Error { error } = response.response else {
+            panic!("expected an error response");
+        };
+        assert_eq!(error.class, "CommandNotFound");
+
+        let event =
+            r#"{"event": "STOP", "timestamp": {"seconds": 1267041653, "microseconds": 9518}}"#;
+        let AnyResponse::Event(event) = event.parse().unwrap() else {
+            panic!("expected an event");
+        };
+        assert_eq!(event.event, "STOP");
+
+        assert!("{}".parse::<AnyResponse>().is_err());
+        assert!("[]".parse::<AnyResponse>().is_err());
+    }
+
+    #[test]
+    fn any_command_round_trip() {
+        let string = r#"{"execute":"query-status","id":1}"#;
+        let cmd: AnyCommand<Value, u64> = serde_json::from_str(string).unwrap();
+        assert_eq!(
+            cmd,
+            AnyCommand::InBand(Command {
+                execute: "query-status".to_string(),
+                arguments: None,
+                id: Some(1),
+            })
+        );
+        assert_eq!(string, serde_json::to_string(&cmd).unwrap());
+
+        let string = r#"{"exec-oob":"migrate-pause","id":2}"#;
+        let cmd: AnyCommand<Value, u64> = serde_json::from_str(string).unwrap();
+        assert_eq!(
+            cmd,
+            AnyCommand::OutOfBand(OobCommand {
+                exec_oob: "migrate-pause".to_string(),
+                arguments: None,
+                id: Some(2),
+            })
+        );
+        assert_eq!(string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn timestamp_datetime_round_trip() {
+        let timestamp = super::Timestamp {
+            seconds: 1267041653,
+            microseconds: 9518,
+        };
+        let datetime = timestamp.to_datetime_utc();
+        let expected = chrono::NaiveDate::from_ymd_opt(2010, 2, 24)
+            .and_then(|date| date.and_hms_micro_opt(20, 0, 53, 9518))
+            .unwrap()
+            .and_utc();
+        assert_eq!(datetime, expected);
+        assert_eq!(super::Timestamp::from_datetime(&datetime), timestamp);
+    }
+
+    #[test]
+    fn id_generator_increasing() {
+        let mut ids = IdGenerator::new();
+        let first = ids.next().unwrap();
+        let second = ids.next().unwrap();
+        assert!(second > first);
+        let cmd = crate::query_version().with_id(ids.next().unwrap());
+        assert_eq!(cmd.id, Some(2));
+        let names = ["stop", "cont"];
+        let ids: Vec<(u64, &str)> = ids.zip(names).collect();
+        assert_eq!(ids, [(3, "stop"), (4, "cont")]);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn new_raw_command() {
+        let cmd = Command::raw(
+            "x-debug-foo",
+            Some(serde_json::json!({"level": 3, "verbose": true})),
+        );
+        let compact_string = r#"{"execute":"x-debug-foo","arguments":{"level":3,"verbose":true}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let cmd = Command::raw("x-debug-bar", None).with_id(7);
+        let compact_string = r#"{"execute":"x-debug-bar","id":7}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn oob_command_try_from_command() {
+        use crate::{MigratePause, migrate_pause, migrate_recover};
+        let cmd = migrate_recover("tcp:192.168.1.200:12345".to_string()).with_id(1);
+        let oob = OobCommand::try_from_command(cmd).unwrap();
+        let compact_string = r#"{"exec-oob":"migrate-recover","arguments":{"uri":"tcp:192.168.1.200:12345"},"id":1}"#;
+        assert_eq!(compact_string, serde_json::to_string(&oob).unwrap());
+        let mut cmd = migrate_recover("tcp:192.168.1.200:12345".to_string());
+        cmd.execute = "migrate-incoming".to_string();
+        assert_eq!(OobCommand::try_from_command(cmd.clone()), Err(cmd));
+        let oob = OobCommand::<(), ()>::from_marker::<MigratePause>(None);
+        let compact_string = r#"{"exec-oob":"migrate-pause"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&oob).unwrap());
+        assert_eq!(oob, OobCommand::from_command(migrate_pause()));
+    }
+
+    #[test]
+    fn return_or_error_permissive_value() {
+        let string = r#"{"error": {"class": "GenericError", "desc": "boom"}, "id": 3}"#;
+        let response: Response<Value, u64> = serde_json::from_str(string).unwrap();
+        let ReturnOrError::Error { error } = response.response else {
+            panic!("error response parsed as success");
+        };
+        assert_eq!(error.class, "GenericError");
+        assert_eq!(response.id, Some(3));
+
+        let string = r#"{"return": "somestring"}"#;
+        let response: Response<Value, u64> = serde_json::from_str(string).unwrap();
+        assert_eq!(
+            response.response,
+            ReturnOrError::Return {
+                value: Value::String("somestring".to_string())
+            }
+        );
+
+        let string = r#"{"error": {"class": "GenericError", "desc": "boom"}}"#;
+        let response: Response<Option<Value>, u64> = serde_json::from_str(string).unwrap();
+        assert!(matches!(response.response, ReturnOrError::Error { .. }));
+
+        let string = r#"{"return": {}, "error": {"class": "GenericError", "desc": "boom"}}"#;
+        assert!(serde_json::from_str::<Response<Value, u64>>(string).is_err());
+        assert!(serde_json::from_str::<Response<Value, u64>>(r#"{"id": 1}"#).is_err());
+    }
+
     // TODO fn qmp_documentation_example_2
     /*
     C: { "execute": "eject", "arguments": { "device": "ide1-cd0" } }
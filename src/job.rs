@@ -0,0 +1,193 @@
+//! Background jobs.
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::generic::{Command, QmpCommand};
+
+/// Type of a background job.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum JobType {
+    /// Block commit job type.
+    Commit,
+    /// Block stream job type.
+    Stream,
+    /// Drive mirror job type.
+    Mirror,
+    /// Drive backup job type.
+    Backup,
+    /// Image creation job type.
+    Create,
+    /// Image options amend job type.
+    Amend,
+    /// Snapshot load job type.
+    SnapshotLoad,
+    /// Snapshot save job type.
+    SnapshotSave,
+    /// Snapshot delete job type.
+    SnapshotDelete,
+}
+
+/// Indicates the present state of a given job in its lifetime.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JobStatus {
+    /// Erroneous, default state. Should not ever be visible.
+    Undefined,
+    /// The job has been created, but not yet started.
+    Created,
+    /// The job is currently running.
+    Running,
+    /// The job is running, but paused. The pause may be requested by
+    /// either the QMP user or by internal processes.
+    Paused,
+    /// The job is running, but is ready for the user to signal completion.
+    Ready,
+    /// The job is ready, but paused.
+    Standby,
+    /// The job is waiting for other jobs in the transaction to converge
+    /// to the waiting state.
+    Waiting,
+    /// The job has finished its work, but has finalization steps that it
+    /// needs to make prior to completing.
+    Pending,
+    /// The job is in the process of being aborted, and will finish with
+    /// an error.
+    Aborting,
+    /// The job has finished all work. If auto-dismiss was set to false,
+    /// the job will remain in the query list until it is dismissed.
+    Concluded,
+    /// The job is in the process of being dismantled. This state should
+    /// not ever be visible externally.
+    Null,
+}
+
+/// Information about a job.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct JobInfo {
+    /// The job identifier.
+    pub id: String,
+    /// The kind of job that is being performed.
+    #[serde(rename = "type")]
+    pub job_type: JobType,
+    /// Current job state/status.
+    pub status: JobStatus,
+    /// Progress made until now.
+    ///
+    /// The unit is arbitrary and the value can only meaningfully be used
+    /// for the ratio of `current_progress` to `total_progress`.
+    pub current_progress: i64,
+    /// Estimated `current_progress` value at the completion of the job.
+    pub total_progress: i64,
+    /// If this field is present, the job failed; it contains a human-readable
+    /// error message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl JobInfo {
+    /// Progress of the job as a fraction of its estimated total.
+    ///
+    /// Returns `None` while the total progress is not known yet (zero).
+    #[inline]
+    pub fn progress_fraction(&self) -> Option<f64> {
+        if self.total_progress <= 0 {
+            return None;
+        }
+        Some(self.current_progress as f64 / self.total_progress as f64)
+    }
+}
+
+/// A job of type 'backup' in the result of 'query-jobs'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BackupJobInfo<'a> {
+    info: &'a JobInfo,
+}
+
+impl<'a> BackupJobInfo<'a> {
+    /// View the job as a backup job, if it is one.
+    #[inline]
+    pub fn from_job(info: &'a JobInfo) -> Option<Self> {
+        (info.job_type == JobType::Backup).then_some(BackupJobInfo { info })
+    }
+
+    /// Information about the job.
+    #[inline]
+    pub fn info(&self) -> &'a JobInfo {
+        self.info
+    }
+
+    /// Progress of the backup as a fraction of its estimated total.
+    #[inline]
+    pub fn progress_fraction(&self) -> Option<f64> {
+        self.info.progress_fraction()
+    }
+}
+
+/// Filter the backup jobs from the result of 'query-jobs'.
+#[inline]
+pub fn backup_jobs(jobs: &[JobInfo]) -> impl Iterator<Item = BackupJobInfo<'_>> {
+    jobs.iter().filter_map(BackupJobInfo::from_job)
+}
+
+/// Marker of 'query-jobs'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryJobs;
+
+impl QmpCommand for QueryJobs {
+    type Return = Vec<JobInfo>;
+    const NAME: &'static str = "query-jobs";
+}
+
+/// Return information about jobs.
+#[inline]
+pub fn query_jobs() -> Command<(), ()> {
+    Command {
+        execute: "query-jobs".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Emitted when a job transitions to a different status.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct JobStatusChangeEvent {
+    /// The job identifier.
+    pub id: String,
+    /// The new job status.
+    pub status: JobStatus,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JobInfo, JobStatus, JobType, backup_jobs, query_jobs};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn query_jobs_result() {
+        let cmd = query_jobs();
+        let compact_string = r#"{"execute":"query-jobs"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[
+            {"id": "backup0", "type": "backup", "status": "running",
+             "current-progress": 536870912, "total-progress": 1073741824},
+            {"id": "mirror0", "type": "mirror", "status": "ready",
+             "current-progress": 1073741824, "total-progress": 1073741824},
+            {"id": "backup1", "type": "backup", "status": "created",
+             "current-progress": 0, "total-progress": 0}
+        ]"#;
+        let jobs: Vec<JobInfo> = serde_json::from_str(string).unwrap();
+        assert_eq!(jobs[1].job_type, JobType::Mirror);
+        assert_eq!(jobs[1].status, JobStatus::Ready);
+        let backups: Vec<_> = backup_jobs(&jobs).collect();
+        assert_eq!(backups.len(), 2);
+        assert_eq!(backups[0].info().id, "backup0");
+        assert_eq!(backups[0].progress_fraction(), Some(0.5));
+        assert_eq!(backups[1].progress_fraction(), None);
+    }
+}
@@ -1,10 +1,381 @@
 //! QEMU Machine Protocol (QMP) structure representation library.
+//!
+//! This library is `no_std` and only requires `alloc`. The `json` feature
+//! provides commands and responses carrying raw `serde_json::Value`s, such
+//! as `Command::raw`, `AnyResponse` or `construct_command`; without it,
+//! serde_json is not a dependency. The `events` feature, enabled by default,
+//! provides the `events` module dispatching any event received from the
+//! server and enables `json`; the generic `Event` structure is always
+//! available. The `std` feature, also enabled by default, provides the
+//! client-side `transport` module and enables `events`.
+#![no_std]
 
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "events")]
+pub mod events;
 pub mod generic;
+#[cfg(feature = "std")]
+pub mod transport;
 
+mod acpi;
+mod block;
+mod char_dev;
+#[cfg(feature = "json")]
+mod cpu;
+mod job;
+mod machine;
+mod migration;
+mod misc;
+mod net;
+mod pci;
+mod qdev;
 mod qmp_monitor;
+#[cfg(feature = "json")]
+mod qom;
+mod registry;
+mod replay;
+mod rocker;
+mod sockets;
+mod stats;
+mod tpm;
+mod trace;
+mod ui;
 mod vm_run_state;
+mod yank;
 // TODO other modules.
 
-pub use qmp_monitor::{query_version, set_action, watchdog_set_action};
-// TODO pub use all the command constructors.
+pub use acpi::{
+    AcpiDeviceOstEvent, AcpiOstInfo, AcpiSlotType, QueryAcpiOspmStatus, query_acpi_ospm_status,
+};
+pub use block::{
+    BlockDeviceInfo, BlockDeviceIoStatus, BlockDeviceStats, BlockDeviceTimedStats, BlockDirtyInfo,
+    BlockInfo, BlockJobInfo, BlockLatencyHistogramInfo, BlockStats, BlockdevAioOptions,
+    BlockdevCacheInfo, BlockdevChangeMediumParams, BlockdevChangeReadOnlyMode,
+    BlockdevCloseTrayParams, BlockdevDetectZeroesOptions, BlockdevOpenTrayParams, BlockdevOptions,
+    BlockdevOptionsFile, BlockdevOptionsQcow2, BlockdevRef, BlockdevRefOrNull, EjectParams,
+    ImageInfo, PrManagerInfo, PrManagerStatusChangedEvent, QueryBlock, QueryBlockJobs,
+    QueryBlockstatsParams, QueryNamedBlockNodesParams, QueryPrManagers, QuorumFailureEvent,
+    QuorumOpType, QuorumReportBadEvent, SnapshotInfo, blockdev_add_typed, blockdev_change_medium,
+    blockdev_close_tray, blockdev_open_tray, eject, query_block, query_block_jobs,
+    query_blockstats, query_named_block_nodes, query_pr_managers,
+};
+#[cfg(feature = "json")]
+pub use block::{
+    BlockdevReopenParams, BlockdevValidationError, blockdev_add, blockdev_reopen,
+    validate_blockdev_options,
+};
+#[cfg(feature = "json")]
+pub use char_dev::chardev_add;
+pub use char_dev::{
+    ChardevAddParams, ChardevBackend, ChardevCommon, ChardevFile, ChardevHostdev, ChardevReturn,
+    ChardevRingbuf, ChardevSocket, ChardevStdio, ChardevVc, VserportChangeEvent, chardev_add_typed,
+};
+#[cfg(feature = "json")]
+pub use cpu::{
+    CpuModelBaselineInfo, CpuModelCompareInfo, CpuModelCompareResult, CpuModelInfo,
+    QueryCpuModelBaselineParams, QueryCpuModelComparisonParams, query_cpu_model_baseline,
+    query_cpu_model_comparison,
+};
+pub use job::{
+    BackupJobInfo, JobInfo, JobStatus, JobStatusChangeEvent, JobType, QueryJobs, backup_jobs,
+    query_jobs,
+};
+pub use machine::{
+    CpuAddParams, CurrentMachineParams, GuidInfo, MachineInfo, MemoryDeviceSizeChangeEvent,
+    MemoryInfo, QueryCurrentMachine, QueryMachines, QueryMemorySizeSummary, QueryVmGenerationId,
+    SystemPowerdown, SystemReset, cpu_add, default_machine, query_current_machine, query_machines,
+    query_memory_size_summary, query_vm_generation_id, resolve_machine, system_powerdown,
+    system_reset,
+};
+pub use migration::{
+    BitmapMigrationBitmapAlias, BitmapMigrationBitmapAliasTransform, BitmapMigrationNodeAlias,
+    CalcDirtyRateParams, CancelVcpuDirtyLimitParams, ColoExitReason, ColoMode, ColoStatus,
+    DirtyLimitInfo, DirtyRateInfo, DirtyRateMeasureMode, DirtyRateStatus, DirtyRateVcpu, MigMode,
+    MigrateIncomingParams, MigrateParameters, MigratePause, MigrateRecoverParams,
+    MigrateSetCapabilitiesParams, MigrateStartPostcopy, MigrationCapability,
+    MigrationCapabilityStatus, MigrationChannel, MigrationChannelType, MigrationInfo,
+    MigrationPassEvent, MigrationStats, MigrationStatus, MultiFdCompression, QueryColoStatus,
+    QueryDirtyRate, QueryMigrate, QueryMigrateCapabilities, QueryMigrateParameters,
+    QueryVcpuDirtyLimit, QueryXenReplicationStatus, ReplicationStatus, SetVcpuDirtyLimitParams,
+    SnapshotDeleteParams, SnapshotLoadParams, SnapshotSaveParams, TimeUnit, UnplugPrimaryEvent,
+    VfioStats, XColoLostHeartbeat, XbzrleCacheStats, XenLoadDevicesStateParams,
+    XenSaveDevicesStateParams, ZeroPageDetection, calc_dirty_rate, cancel_vcpu_dirty_limit,
+    migrate_incoming, migrate_pause, migrate_recover, migrate_set_capabilities,
+    migrate_set_parameters, migrate_start_postcopy, query_colo_status, query_dirty_rate,
+    query_migrate, query_migrate_capabilities, query_migrate_parameters, query_vcpu_dirty_limit,
+    query_xen_replication_status, set_vcpu_dirty_limit, snapshot_delete, snapshot_load,
+    snapshot_save, x_colo_lost_heartbeat, xen_load_devices_state, xen_save_devices_state,
+};
+pub use misc::{
+    CommandLineOptionInfo, CommandLineParameterInfo, CommandLineParameterType, Cont, FdsetFdInfo,
+    FdsetInfo, HumanMonitorCommandParams, IoThreadInfo, ParamValue, QueryCommandLineOptionsParams,
+    QueryFdsets, QueryIothreads, QuerySgx, QuerySgxCapabilities, Quit, RtcChangeEvent,
+    SgxEpcSection, SgxInfo, Stop, cont, human_monitor_command, parse_hmp_lines,
+    query_command_line_options, query_fdsets, query_iothreads, query_sgx, query_sgx_capabilities,
+    quit, stop,
+};
+pub use net::FailoverNegotiatedEvent;
+pub use pci::PciDeviceClass;
+pub use qdev::DeviceUnplugGuestErrorEvent;
+#[cfg(feature = "json")]
+pub use qmp_monitor::parse_version_response;
+pub use qmp_monitor::{
+    CommandInfo, CommandInfoSet, PanicAction, QmpCapabilitiesParams, QmpCapability, QueryCommands,
+    QueryVersion, RebootAction, SetActionParams, SetActionParamsBuilder, ShutdownAction,
+    VersionInfo, VersionTriple, WatchdogAction, WatchdogActionParseError, WatchdogSetActionParams,
+    qmp_capabilities, query_commands, query_version, set_action, watchdog_set_action,
+    watchdog_set_action_from_str,
+};
+#[cfg(feature = "json")]
+pub use qom::{
+    HostMemPolicy, MemoryBackendRamOpts, ObjectAddParams, QomSetParams, object_add,
+    object_add_memory_backend_ram, qom_set, qom_set_bool, qom_set_str,
+};
+pub use registry::COMMAND_NAMES;
+#[cfg(feature = "json")]
+pub use registry::construct_command;
+pub use replay::{
+    QueryReplay, ReplayBreakParams, ReplayInfo, ReplayMode, ReplaySeekParams, query_replay,
+    replay_break, replay_seek,
+};
+pub use rocker::{
+    QueryRockerOfDpaFlowsParams, QueryRockerParams, QueryRockerPortsParams, RockerOfDpaFlow,
+    RockerOfDpaFlowAction, RockerOfDpaFlowKey, RockerOfDpaFlowMask, RockerPort, RockerPortAutoneg,
+    RockerPortDuplex, RockerSwitch, query_rocker, query_rocker_of_dpa_flows, query_rocker_ports,
+};
+pub use sockets::{
+    FdSocketAddress, InetSocketAddress, NetworkAddressFamily, SocketAddress, SocketAddressLegacy,
+    SocketAddressParseError, UnixSocketAddress, VsockSocketAddress,
+};
+pub use stats::{
+    QueryStatsSchemasParams, Stat, StatsFilter, StatsProvider, StatsRequest, StatsResult,
+    StatsSchema, StatsSchemaValue, StatsTarget, StatsType, StatsUnit, StatsValue, query_stats,
+    query_stats_schemas,
+};
+pub use tpm::{
+    QueryTpm, QueryTpmModels, QueryTpmTypes, TpmEmulatorOptions, TpmInfo, TpmModel,
+    TpmPassthroughOptions, TpmType, TpmTypeOptions, query_tpm, query_tpm_models, query_tpm_types,
+};
+pub use trace::{
+    TraceEventGetStateParams, TraceEventInfo, TraceEventSetStateParams, TraceEventState,
+    trace_event_get_state, trace_event_set_state,
+};
+pub use ui::{
+    DisplayAuth, DisplayReloadOptions, DisplayReloadOptionsVnc, QuerySpice, QueryVnc,
+    SpiceBasicInfo, SpiceChannel, SpiceClientInfo, SpiceConnectedEvent, SpiceDisconnectedEvent,
+    SpiceInfo, SpiceInitializedEvent, SpiceQueryMouseMode, SpiceServerInfo, VncClientInfo, VncInfo,
+    display_reload, query_spice, query_vnc,
+};
+pub use vm_run_state::{
+    GuestCrashloadedEvent, GuestPanicAction, GuestPanicInformation, GuestPanicInformationHyperV,
+    GuestPanicInformationS390, GuestPanickedEvent, ResetEvent, S390CrashReason, ShutdownCause,
+    ShutdownEvent,
+};
+pub use yank::{
+    QueryYank, YankInstance, YankInstanceBlockNode, YankInstanceChardev, YankParams, query_yank,
+    yank,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BlockdevChangeMediumParams, BlockdevOptions, BlockdevOptionsFile, CalcDirtyRateParams,
+        ChardevBackend, ChardevCommon, DisplayReloadOptions, DisplayReloadOptionsVnc,
+        MigrateParameters, SetActionParams, SnapshotDeleteParams, SnapshotLoadParams,
+        SnapshotSaveParams, StatsFilter, StatsTarget, WatchdogAction, blockdev_add_typed,
+        blockdev_change_medium, blockdev_close_tray, blockdev_open_tray, calc_dirty_rate,
+        cancel_vcpu_dirty_limit, chardev_add_typed, cont, cpu_add, display_reload, eject,
+        generic::Command, human_monitor_command, migrate_incoming, migrate_pause, migrate_recover,
+        migrate_set_capabilities, migrate_set_parameters, migrate_start_postcopy, qmp_capabilities,
+        query_acpi_ospm_status, query_block, query_block_jobs, query_blockstats, query_colo_status,
+        query_command_line_options, query_commands, query_current_machine, query_dirty_rate,
+        query_fdsets, query_iothreads, query_jobs, query_machines, query_memory_size_summary,
+        query_migrate, query_migrate_capabilities, query_migrate_parameters,
+        query_named_block_nodes, query_pr_managers, query_replay, query_rocker,
+        query_rocker_of_dpa_flows, query_rocker_ports, query_sgx, query_sgx_capabilities,
+        query_spice, query_stats, query_stats_schemas, query_tpm, query_tpm_models,
+        query_tpm_types, query_vcpu_dirty_limit, query_version, query_vm_generation_id, query_vnc,
+        query_xen_replication_status, query_yank, quit, replay_break, replay_seek, set_action,
+        set_vcpu_dirty_limit, snapshot_delete, snapshot_load, snapshot_save, stop,
+        system_powerdown, system_reset, trace_event_get_state, trace_event_set_state,
+        watchdog_set_action, x_colo_lost_heartbeat, xen_load_devices_state, xen_save_devices_state,
+        yank,
+    };
+    #[cfg(feature = "json")]
+    use super::{
+        CpuModelInfo, MemoryBackendRamOpts, blockdev_add, blockdev_reopen, chardev_add, object_add,
+        object_add_memory_backend_ram, qom_set, query_cpu_model_baseline,
+        query_cpu_model_comparison,
+    };
+    use alloc::{string::ToString, vec, vec::Vec};
+    use serde::Serialize;
+    #[cfg(feature = "json")]
+    use serde_json::Map;
+    use serde_json::Value;
+
+    fn json<T: Serialize>(cmd: Command<T, ()>) -> Value {
+        serde_json::to_value(cmd).unwrap()
+    }
+
+    #[cfg(feature = "json")]
+    fn cpu_model(name: &str) -> CpuModelInfo {
+        CpuModelInfo {
+            name: name.to_string(),
+            props: None,
+        }
+    }
+
+    /// Constructor name, closure building the command, and expected JSON.
+    type Case = (&'static str, fn() -> Value, &'static str);
+
+    /// Every constructor with all optional arguments omitted, and the exact
+    /// JSON it must produce.
+    #[rustfmt::skip]
+    const CONSTRUCTORS: &[Case] = &[
+        ("query-acpi-ospm-status", || json(query_acpi_ospm_status()), r#"{"execute":"query-acpi-ospm-status"}"#),
+        ("query-named-block-nodes", || json(query_named_block_nodes(None)), r#"{"execute":"query-named-block-nodes","arguments":{}}"#),
+        ("eject", || json(eject(None, Some("ide0-1-0".to_string()), None)), r#"{"execute":"eject","arguments":{"id":"ide0-1-0"}}"#),
+        ("blockdev-open-tray", || json(blockdev_open_tray(None, Some("ide0-1-0".to_string()), None)), r#"{"execute":"blockdev-open-tray","arguments":{"id":"ide0-1-0"}}"#),
+        ("blockdev-close-tray", || json(blockdev_close_tray(None, Some("ide0-1-0".to_string()))), r#"{"execute":"blockdev-close-tray","arguments":{"id":"ide0-1-0"}}"#),
+        ("blockdev-change-medium", || json(blockdev_change_medium(BlockdevChangeMediumParams {
+            device: None,
+            id: Some("ide0-1-0".to_string()),
+            filename: "/srv/images/Fedora-12-x86_64-DVD.iso".to_string(),
+            format: None,
+            force: None,
+            read_only_mode: None,
+        })), r#"{"execute":"blockdev-change-medium","arguments":{"id":"ide0-1-0","filename":"/srv/images/Fedora-12-x86_64-DVD.iso"}}"#),
+        ("query-block", || json(query_block()), r#"{"execute":"query-block"}"#),
+        ("query-block-jobs", || json(query_block_jobs()), r#"{"execute":"query-block-jobs"}"#),
+        ("query-blockstats", || json(query_blockstats(None)), r#"{"execute":"query-blockstats","arguments":{}}"#),
+        ("query-pr-managers", || json(query_pr_managers()), r#"{"execute":"query-pr-managers"}"#),
+        #[cfg(feature = "json")]
+        ("blockdev-add", || json(blockdev_add(serde_json::json!({"driver":"null-co","node-name":"null0"}))), r#"{"execute":"blockdev-add","arguments":{"driver":"null-co","node-name":"null0"}}"#),
+        ("blockdev-add", || json(blockdev_add_typed(BlockdevOptions::File(BlockdevOptionsFile {
+            node_name: None,
+            read_only: None,
+            filename: "disk.img".to_string(),
+            pr_manager: None,
+            aio: None,
+        }))), r#"{"execute":"blockdev-add","arguments":{"driver":"file","filename":"disk.img"}}"#),
+        #[cfg(feature = "json")]
+        ("blockdev-reopen", || json(blockdev_reopen(vec![serde_json::json!({"driver":"null-co","node-name":"null0"})])), r#"{"execute":"blockdev-reopen","arguments":{"options":[{"driver":"null-co","node-name":"null0"}]}}"#),
+        #[cfg(feature = "json")]
+        ("chardev-add", || json(chardev_add("null0".to_string(), serde_json::json!({"type":"null","data":{}}))), r#"{"execute":"chardev-add","arguments":{"backend":{"type":"null","data":{}},"id":"null0"}}"#),
+        ("chardev-add", || json(chardev_add_typed("null0".to_string(), ChardevBackend::Null(ChardevCommon::default()))), r#"{"execute":"chardev-add","arguments":{"id":"null0","backend":{"type":"null","data":{}}}}"#),
+        #[cfg(feature = "json")]
+        ("query-cpu-model-comparison", || json(query_cpu_model_comparison(cpu_model("host"), cpu_model("max"))), r#"{"execute":"query-cpu-model-comparison","arguments":{"modela":{"name":"host"},"modelb":{"name":"max"}}}"#),
+        #[cfg(feature = "json")]
+        ("query-cpu-model-baseline", || json(query_cpu_model_baseline(cpu_model("host"), cpu_model("max"))), r#"{"execute":"query-cpu-model-baseline","arguments":{"modela":{"name":"host"},"modelb":{"name":"max"}}}"#),
+        ("query-jobs", || json(query_jobs()), r#"{"execute":"query-jobs"}"#),
+        ("query-current-machine", || json(query_current_machine()), r#"{"execute":"query-current-machine"}"#),
+        ("query-machines", || json(query_machines()), r#"{"execute":"query-machines"}"#),
+        ("cpu-add", || json(cpu_add(2)), r#"{"execute":"cpu-add","arguments":{"id":2}}"#),
+        ("query-vm-generation-id", || json(query_vm_generation_id()), r#"{"execute":"query-vm-generation-id"}"#),
+        ("query-memory-size-summary", || json(query_memory_size_summary()), r#"{"execute":"query-memory-size-summary"}"#),
+        ("system_reset", || json(system_reset()), r#"{"execute":"system_reset"}"#),
+        ("system_powerdown", || json(system_powerdown()), r#"{"execute":"system_powerdown"}"#),
+        ("migrate-incoming", || json(migrate_incoming("tcp:0:4446".to_string(), None)), r#"{"execute":"migrate-incoming","arguments":{"uri":"tcp:0:4446"}}"#),
+        ("calc-dirty-rate", || json(calc_dirty_rate(CalcDirtyRateParams {
+            calc_time: 1,
+            calc_time_unit: None,
+            sample_pages: None,
+            mode: None,
+        })), r#"{"execute":"calc-dirty-rate","arguments":{"calc-time":1}}"#),
+        ("query-dirty-rate", || json(query_dirty_rate()), r#"{"execute":"query-dirty-rate"}"#),
+        ("set-vcpu-dirty-limit", || json(set_vcpu_dirty_limit(None, 200)), r#"{"execute":"set-vcpu-dirty-limit","arguments":{"dirty-rate":200}}"#),
+        ("cancel-vcpu-dirty-limit", || json(cancel_vcpu_dirty_limit(None)), r#"{"execute":"cancel-vcpu-dirty-limit","arguments":{}}"#),
+        ("query-vcpu-dirty-limit", || json(query_vcpu_dirty_limit()), r#"{"execute":"query-vcpu-dirty-limit"}"#),
+        ("migrate-start-postcopy", || json(migrate_start_postcopy()), r#"{"execute":"migrate-start-postcopy"}"#),
+        ("migrate-pause", || json(migrate_pause()), r#"{"execute":"migrate-pause"}"#),
+        ("migrate-recover", || json(migrate_recover("tcp:192.168.1.200:12345".to_string())), r#"{"execute":"migrate-recover","arguments":{"uri":"tcp:192.168.1.200:12345"}}"#),
+        ("migrate-set-capabilities", || json(migrate_set_capabilities(Vec::new())), r#"{"execute":"migrate-set-capabilities","arguments":{"capabilities":[]}}"#),
+        ("migrate-set-parameters", || json(migrate_set_parameters(MigrateParameters::default())), r#"{"execute":"migrate-set-parameters","arguments":{}}"#),
+        ("query-migrate", || json(query_migrate()), r#"{"execute":"query-migrate"}"#),
+        ("query-migrate-capabilities", || json(query_migrate_capabilities()), r#"{"execute":"query-migrate-capabilities"}"#),
+        ("query-migrate-parameters", || json(query_migrate_parameters()), r#"{"execute":"query-migrate-parameters"}"#),
+        ("query-xen-replication-status", || json(query_xen_replication_status()), r#"{"execute":"query-xen-replication-status"}"#),
+        ("query-colo-status", || json(query_colo_status()), r#"{"execute":"query-colo-status"}"#),
+        ("x-colo-lost-heartbeat", || json(x_colo_lost_heartbeat()), r#"{"execute":"x-colo-lost-heartbeat"}"#),
+        ("snapshot-save", || json(snapshot_save(SnapshotSaveParams {
+            job_id: "snapsave0".to_string(),
+            tag: "my-snap".to_string(),
+            vmstate: "disk0".to_string(),
+            devices: vec!["disk0".to_string()],
+        })), r#"{"execute":"snapshot-save","arguments":{"job-id":"snapsave0","tag":"my-snap","vmstate":"disk0","devices":["disk0"]}}"#),
+        ("snapshot-load", || json(snapshot_load(SnapshotLoadParams {
+            job_id: "snapload0".to_string(),
+            tag: "my-snap".to_string(),
+            vmstate: "disk0".to_string(),
+            devices: vec!["disk0".to_string()],
+        })), r#"{"execute":"snapshot-load","arguments":{"job-id":"snapload0","tag":"my-snap","vmstate":"disk0","devices":["disk0"]}}"#),
+        ("snapshot-delete", || json(snapshot_delete(SnapshotDeleteParams {
+            job_id: "snapdelete0".to_string(),
+            tag: "my-snap".to_string(),
+            devices: vec!["disk0".to_string()],
+        })), r#"{"execute":"snapshot-delete","arguments":{"job-id":"snapdelete0","tag":"my-snap","devices":["disk0"]}}"#),
+        ("xen-save-devices-state", || json(xen_save_devices_state("/tmp/save".to_string(), None)), r#"{"execute":"xen-save-devices-state","arguments":{"filename":"/tmp/save"}}"#),
+        ("xen-load-devices-state", || json(xen_load_devices_state("/tmp/resume".to_string())), r#"{"execute":"xen-load-devices-state","arguments":{"filename":"/tmp/resume"}}"#),
+        ("query-iothreads", || json(query_iothreads()), r#"{"execute":"query-iothreads"}"#),
+        ("query-fdsets", || json(query_fdsets()), r#"{"execute":"query-fdsets"}"#),
+        ("query-sgx", || json(query_sgx()), r#"{"execute":"query-sgx"}"#),
+        ("query-sgx-capabilities", || json(query_sgx_capabilities()), r#"{"execute":"query-sgx-capabilities"}"#),
+        ("stop", || json(stop()), r#"{"execute":"stop"}"#),
+        ("cont", || json(cont()), r#"{"execute":"cont"}"#),
+        ("quit", || json(quit()), r#"{"execute":"quit"}"#),
+        ("human-monitor-command", || json(human_monitor_command("info kvm".to_string(), None)), r#"{"execute":"human-monitor-command","arguments":{"command-line":"info kvm"}}"#),
+        ("query-command-line-options", || json(query_command_line_options(None)), r#"{"execute":"query-command-line-options","arguments":{}}"#),
+        ("qmp_capabilities", || json(qmp_capabilities(None)), r#"{"execute":"qmp_capabilities","arguments":{}}"#),
+        ("query-version", || json(query_version()), r#"{"execute":"query-version"}"#),
+        ("watchdog-set-action", || json(watchdog_set_action(WatchdogAction::Reset)), r#"{"execute":"watchdog-set-action","arguments":{"action":"reset"}}"#),
+        ("set-action", || json(set_action(SetActionParams::builder().build())), r#"{"execute":"set-action","arguments":{}}"#),
+        ("query-commands", || json(query_commands()), r#"{"execute":"query-commands"}"#),
+        #[cfg(feature = "json")]
+        ("object-add", || json(object_add("iothread".to_string(), "iothread0".to_string(), Map::new())), r#"{"execute":"object-add","arguments":{"qom-type":"iothread","id":"iothread0"}}"#),
+        #[cfg(feature = "json")]
+        ("object-add", || json(object_add_memory_backend_ram("mem0".to_string(), 1048576, MemoryBackendRamOpts::default())), r#"{"execute":"object-add","arguments":{"qom-type":"memory-backend-ram","id":"mem0","size":1048576}}"#),
+        #[cfg(feature = "json")]
+        ("qom-set", || json(qom_set("/machine".to_string(), "graphics".to_string(), Value::Bool(false))), r#"{"execute":"qom-set","arguments":{"path":"/machine","property":"graphics","value":false}}"#),
+        ("query-replay", || json(query_replay()), r#"{"execute":"query-replay"}"#),
+        ("replay-break", || json(replay_break(220414)), r#"{"execute":"replay-break","arguments":{"icount":220414}}"#),
+        ("replay-seek", || json(replay_seek(220414)), r#"{"execute":"replay-seek","arguments":{"icount":220414}}"#),
+        ("query-rocker", || json(query_rocker("sw1".to_string())), r#"{"execute":"query-rocker","arguments":{"name":"sw1"}}"#),
+        ("query-rocker-ports", || json(query_rocker_ports("sw1".to_string())), r#"{"execute":"query-rocker-ports","arguments":{"name":"sw1"}}"#),
+        ("query-rocker-of-dpa-flows", || json(query_rocker_of_dpa_flows("sw1".to_string(), None)), r#"{"execute":"query-rocker-of-dpa-flows","arguments":{"name":"sw1"}}"#),
+        ("query-stats", || json(query_stats(StatsFilter {
+            target: StatsTarget::Vm,
+            providers: None,
+            vcpus: None,
+        })), r#"{"execute":"query-stats","arguments":{"target":"vm"}}"#),
+        ("query-stats-schemas", || json(query_stats_schemas(None)), r#"{"execute":"query-stats-schemas","arguments":{}}"#),
+        ("query-tpm", || json(query_tpm()), r#"{"execute":"query-tpm"}"#),
+        ("query-tpm-models", || json(query_tpm_models()), r#"{"execute":"query-tpm-models"}"#),
+        ("query-tpm-types", || json(query_tpm_types()), r#"{"execute":"query-tpm-types"}"#),
+        ("trace-event-get-state", || json(trace_event_get_state("qemu_memalign".to_string(), None)), r#"{"execute":"trace-event-get-state","arguments":{"name":"qemu_memalign"}}"#),
+        ("trace-event-set-state", || json(trace_event_set_state("qemu_memalign".to_string(), true, None, None)), r#"{"execute":"trace-event-set-state","arguments":{"name":"qemu_memalign","enable":true}}"#),
+        ("display-reload", || json(display_reload(DisplayReloadOptions::Vnc(DisplayReloadOptionsVnc::default()))), r#"{"execute":"display-reload","arguments":{"type":"vnc"}}"#),
+        ("query-vnc", || json(query_vnc()), r#"{"execute":"query-vnc"}"#),
+        ("query-spice", || json(query_spice()), r#"{"execute":"query-spice"}"#),
+        ("yank", || json(yank(Vec::new())), r#"{"execute":"yank","arguments":{"instances":[]}}"#),
+        ("query-yank", || json(query_yank()), r#"{"execute":"query-yank"}"#),
+    ];
+
+    #[test]
+    fn constructors_serialize() {
+        for (name, construct, expected) in CONSTRUCTORS {
+            let value = construct();
+            assert_eq!(value["execute"], *name, "wrong execute for {name}");
+            assert!(value.get("id").is_none(), "unexpected id for {name}");
+            if let Some(Value::Object(arguments)) = value.get("arguments") {
+                for (key, argument) in arguments {
+                    assert!(!argument.is_null(), "{name} serialized null `{key}`");
+                }
+            }
+            let expected: Value = serde_json::from_str(expected).unwrap();
+            assert_eq!(expected, value, "unexpected JSON for {name}");
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Machines.
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::generic::{Command, Empty, QmpCommand};
+
+/// Information describing the running machine parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct CurrentMachineParams {
+    /// True if the machine supports wake up from suspend.
+    pub wakeup_suspend_support: bool,
+}
+
+/// Marker of 'query-current-machine'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryCurrentMachine;
+
+impl QmpCommand for QueryCurrentMachine {
+    type Return = CurrentMachineParams;
+    const NAME: &'static str = "query-current-machine";
+}
+
+/// Query machine parameters.
+///
+/// Clients can use `wakeup_suspend_support` of the returned
+/// `CurrentMachineParams` to decide whether 'system_wakeup' is meaningful.
+#[inline]
+pub fn query_current_machine() -> Command<(), ()> {
+    Command {
+        execute: "query-current-machine".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Information describing a machine.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct MachineInfo {
+    /// The name of the machine.
+    pub name: String,
+    /// An alias for the machine name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// Whether the machine is default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_default: Option<bool>,
+    /// Maximum number of CPUs supported by the machine type.
+    pub cpu_max: i64,
+    /// Whether the machine supports hot-plugging CPUs.
+    pub hotpluggable_cpus: bool,
+    /// Whether the machine supports memory on NUMA nodes.
+    pub numa_mem_supported: bool,
+    /// If true, the machine type is deprecated and may be removed in future
+    /// versions of QEMU according to the QEMU deprecation policy.
+    pub deprecated: bool,
+    /// Default CPU model typename if none is requested via the -cpu argument.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_cpu_type: Option<String>,
+    /// The default ID of initial RAM memory backend.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_ram_id: Option<String>,
+    /// Machine type supports ACPI (since QEMU 8.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acpi: Option<bool>,
+}
+
+/// Marker of 'query-machines'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryMachines;
+
+impl QmpCommand for QueryMachines {
+    type Return = Vec<MachineInfo>;
+    const NAME: &'static str = "query-machines";
+}
+
+/// Return a list of supported machines.
+#[inline]
+pub fn query_machines() -> Command<(), ()> {
+    Command {
+        execute: "query-machines".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Find the machine named `requested` in the result of 'query-machines'.
+///
+/// Machine names are matched first, then aliases, so that an alias
+/// such as `virt` resolves to the versioned machine it currently stands for.
+pub fn resolve_machine<'a>(list: &'a [MachineInfo], requested: &str) -> Option<&'a MachineInfo> {
+    list.iter()
+        .find(|machine| machine.name == requested)
+        .or_else(|| {
+            list.iter()
+                .find(|machine| machine.alias.as_deref() == Some(requested))
+        })
+}
+
+/// Find the default machine in the result of 'query-machines'.
+pub fn default_machine(list: &[MachineInfo]) -> Option<&MachineInfo> {
+    list.iter().find(|machine| machine.is_default == Some(true))
+}
+
+/// Arguments of 'cpu-add'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CpuAddParams {
+    /// ID of CPU to be created, valid values [0..max_cpus).
+    pub id: i64,
+}
+
+impl QmpCommand for CpuAddParams {
+    type Return = Empty;
+    const NAME: &'static str = "cpu-add";
+}
+
+/// Add a new CPU.
+///
+/// Deprecated: this command was removed in QEMU 5.2, use 'device_add' instead.
+/// It is kept for management stacks talking to older QEMU releases.
+#[inline]
+pub fn cpu_add(id: i64) -> Command<CpuAddParams, ()> {
+    Command {
+        execute: "cpu-add".to_string(),
+        arguments: Some(CpuAddParams { id }),
+        id: None,
+    }
+}
+
+/// GUID information.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct GuidInfo {
+    /// The globally unique identifier.
+    pub guid: String,
+}
+
+/// Marker of 'query-vm-generation-id'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryVmGenerationId;
+
+impl QmpCommand for QueryVmGenerationId {
+    type Return = GuidInfo;
+    const NAME: &'static str = "query-vm-generation-id";
+}
+
+/// Show Virtual Machine Generation ID.
+#[inline]
+pub fn query_vm_generation_id() -> Command<(), ()> {
+    Command {
+        execute: "query-vm-generation-id".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Actual memory information in bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct MemoryInfo {
+    /// Size of "base" memory specified with command line option -m.
+    pub base_memory: u64,
+    /// Size of memory that can be hot-unplugged.
+    ///
+    /// This field is omitted if target doesn't support memory hotplug
+    /// (i.e. CONFIG_MEM_DEVICE not defined at build time).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugged_memory: Option<u64>,
+}
+
+/// Marker of 'query-memory-size-summary'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryMemorySizeSummary;
+
+impl QmpCommand for QueryMemorySizeSummary {
+    type Return = MemoryInfo;
+    const NAME: &'static str = "query-memory-size-summary";
+}
+
+/// Return the amount of initially allocated and present hotpluggable
+/// (if enabled) memory in bytes.
+#[inline]
+pub fn query_memory_size_summary() -> Command<(), ()> {
+    Command {
+        execute: "query-memory-size-summary".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Marker of 'system_reset'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SystemReset;
+
+impl QmpCommand for SystemReset {
+    type Return = Empty;
+    const NAME: &'static str = "system_reset";
+}
+
+/// Performs a hard reset of a guest.
+///
+/// The guest is reset asynchronously; a RESET event is emitted once it is done.
+#[inline]
+pub fn system_reset() -> Command<(), ()> {
+    Command {
+        execute: "system_reset".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Marker of 'system_powerdown'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SystemPowerdown;
+
+impl QmpCommand for SystemPowerdown {
+    type Return = Empty;
+    const NAME: &'static str = "system_powerdown";
+}
+
+/// Requests that a guest perform a powerdown operation.
+///
+/// A guest may or may not respond to this command. This command returning
+/// does not indicate that a guest has accepted the request or that it has
+/// shut down. Many guests will respond to this command by prompting the
+/// user in some way.
+#[inline]
+pub fn system_powerdown() -> Command<(), ()> {
+    Command {
+        execute: "system_powerdown".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Emitted when the size of a memory device changes.
+///
+/// Only emitted for memory devices that can actually change the size
+/// (e.g., virtio-mem due to guest action).
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct MemoryDeviceSizeChangeEvent {
+    /// Device's ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The new size of memory that the device provides.
+    pub size: u64,
+    /// Path to the device object in the QOM tree.
+    pub qom_path: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CurrentMachineParams, GuidInfo, MachineInfo, MemoryInfo, cpu_add, default_machine,
+        query_current_machine, query_machines, query_memory_size_summary, query_vm_generation_id,
+        resolve_machine, system_reset,
+    };
+    use alloc::{string::ToString, vec::Vec};
+
+    #[test]
+    fn new_query_current_machine() {
+        let cmd = query_current_machine();
+        let compact_string = r#"{"execute":"query-current-machine"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_current_machine_result() {
+        let string = r#"{"wakeup-suspend-support": true}"#;
+        let value = CurrentMachineParams {
+            wakeup_suspend_support: true,
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn new_cpu_add() {
+        let cmd = cpu_add(1);
+        let compact_string = r#"{"execute":"cpu-add","arguments":{"id":1}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_query_vm_generation_id() {
+        let cmd = query_vm_generation_id();
+        let compact_string = r#"{"execute":"query-vm-generation-id"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_vm_generation_id_result() {
+        let string = r#"{"guid": "324e6eaf-d1d1-4bf6-bf41-b9bb6cd4f306"}"#;
+        let value = GuidInfo {
+            guid: "324e6eaf-d1d1-4bf6-bf41-b9bb6cd4f306".to_string(),
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn new_system_reset() {
+        let cmd = system_reset();
+        let compact_string = r#"{"execute":"system_reset"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    fn sample_machines() -> Vec<MachineInfo> {
+        let string = r#"[
+            {"name": "virt-7.2", "alias": "virt", "cpu-max": 512, "hotpluggable-cpus": true,
+             "numa-mem-supported": true, "deprecated": false, "default-cpu-type": "rv64-riscv-cpu",
+             "default-ram-id": "riscv_virt_board.ram", "acpi": true},
+            {"name": "virt-7.1", "cpu-max": 512, "hotpluggable-cpus": true,
+             "numa-mem-supported": true, "deprecated": false, "acpi": true},
+            {"name": "spike", "is-default": true, "cpu-max": 8, "hotpluggable-cpus": false,
+             "numa-mem-supported": true, "deprecated": false, "acpi": false}
+        ]"#;
+        serde_json::from_str(string).unwrap()
+    }
+
+    #[test]
+    fn new_query_machines() {
+        let cmd = query_machines();
+        let compact_string = r#"{"execute":"query-machines"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn resolve_machine_alias() {
+        let machines = sample_machines();
+        let machine = resolve_machine(&machines, "virt").unwrap();
+        assert_eq!(machine.name, "virt-7.2");
+        let machine = resolve_machine(&machines, "virt-7.1").unwrap();
+        assert_eq!(machine.name, "virt-7.1");
+        assert!(resolve_machine(&machines, "sifive_u").is_none());
+    }
+
+    #[test]
+    fn default_machine_in_list() {
+        let machines = sample_machines();
+        assert_eq!(default_machine(&machines).unwrap().name, "spike");
+        assert!(default_machine(&machines[..2]).is_none());
+    }
+
+    #[test]
+    fn new_query_memory_size_summary() {
+        let cmd = query_memory_size_summary();
+        let compact_string = r#"{"execute":"query-memory-size-summary"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_memory_size_summary_result() {
+        let string = r#"{"base-memory": 4294967296, "plugged-memory": 0}"#;
+        let value = MemoryInfo {
+            base_memory: 4294967296,
+            plugged_memory: Some(0),
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+        let string = r#"{"base-memory": 2147483648, "plugged-memory": 1073741824}"#;
+        let info: MemoryInfo = serde_json::from_str(string).unwrap();
+        assert_eq!(info.plugged_memory, Some(1073741824));
+        let info: MemoryInfo = serde_json::from_str(r#"{"base-memory": 2147483648}"#).unwrap();
+        assert_eq!(info.plugged_memory, None);
+    }
+}
@@ -0,0 +1,1682 @@
+//! Migration.
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::time::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::generic::{Command, Empty, OobSafe, QmpCommand};
+use crate::sockets::SocketAddress;
+
+/// Dirty page rate measurement status.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DirtyRateStatus {
+    /// The dirtyrate thread has not been started.
+    Unstarted,
+    /// The dirtyrate thread is measuring.
+    Measuring,
+    /// The dirtyrate thread has measured and results are available.
+    Measured,
+}
+
+/// Method used to measure dirty page rate.
+///
+/// Differences between available methods are explained in 'calc-dirty-rate'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DirtyRateMeasureMode {
+    /// Use page sampling.
+    PageSampling,
+    /// Use dirty ring.
+    DirtyRing,
+    /// Use dirty bitmap.
+    DirtyBitmap,
+}
+
+/// Specifies unit in which time-related value is specified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimeUnit {
+    /// Value is in seconds.
+    Second,
+    /// Value is in milliseconds.
+    Millisecond,
+}
+
+/// Dirty rate of a virtual CPU.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct DirtyRateVcpu {
+    /// Virtual CPU index.
+    pub id: i64,
+    /// Dirty rate (MB/s) of the virtual CPU.
+    pub dirty_rate: i64,
+}
+
+/// Information about measured dirty page rate.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct DirtyRateInfo {
+    /// An estimate of the dirty page rate of the VM in units of MiB/s.
+    ///
+    /// Value is present only when `status` is 'measured'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dirty_rate: Option<i64>,
+    /// Status of dirty page rate measurement.
+    pub status: DirtyRateStatus,
+    /// Start time in units of second for calculation.
+    pub start_time: i64,
+    /// Time period for which dirty page rate was measured,
+    /// expressed and rounded down to `calc_time_unit`.
+    pub calc_time: i64,
+    /// Time unit of `calc_time`.
+    ///
+    /// Older QEMU releases do not report this member and measure in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub calc_time_unit: Option<TimeUnit>,
+    /// Number of sampled pages per GiB of guest memory.
+    ///
+    /// Valid only in page-sampling mode.
+    pub sample_pages: u64,
+    /// Mode that was used to measure dirty page rate.
+    pub mode: DirtyRateMeasureMode,
+    /// Dirty rate for each vCPU if dirty-ring mode was specified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcpu_dirty_rate: Option<Vec<DirtyRateVcpu>>,
+}
+
+/// Dirty page rate limit information of a virtual CPU.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct DirtyLimitInfo {
+    /// Index of a virtual CPU.
+    pub cpu_index: i64,
+    /// Upper limit of dirty page rate (MB/s) for a virtual CPU,
+    /// 0 means unlimited.
+    pub limit_rate: u64,
+    /// Current dirty page rate (MB/s) for a virtual CPU.
+    pub current_rate: u64,
+}
+
+/// The migration channel type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MigrationChannelType {
+    /// Main outbound migration channel.
+    Main,
+}
+
+/// Migration stream channel parameters.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MigrationChannel {
+    /// Channel type for transferring packet information.
+    pub channel_type: MigrationChannelType,
+    /// Migration endpoint configuration on destination interface.
+    ///
+    /// Only socket endpoints are modeled for now.
+    pub addr: SocketAddress,
+}
+
+/// Arguments of 'migrate-incoming'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MigrateIncomingParams {
+    /// The Uniform Resource Identifier identifying the source or
+    /// address to listen on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    /// List of migration stream channels with each stream in the
+    /// list connected to a destination interface endpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channels: Option<Vec<MigrationChannel>>,
+}
+
+impl QmpCommand for MigrateIncomingParams {
+    type Return = Empty;
+    const NAME: &'static str = "migrate-incoming";
+}
+
+/// Start an incoming migration.
+///
+/// QEMU must have been started with `-incoming defer`. The uri format
+/// is the same as for `-incoming`, for example `tcp:0:4444`.
+#[inline]
+pub fn migrate_incoming(
+    uri: String,
+    channels: Option<Vec<MigrationChannel>>,
+) -> Command<MigrateIncomingParams, ()> {
+    Command {
+        execute: "migrate-incoming".to_string(),
+        arguments: Some(MigrateIncomingParams {
+            uri: Some(uri),
+            channels,
+        }),
+        id: None,
+    }
+}
+
+/// Arguments of 'calc-dirty-rate'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CalcDirtyRateParams {
+    /// Time period for which dirty page rate is calculated.
+    ///
+    /// By default it is specified in seconds, but the unit can be set
+    /// explicitly with `calc_time_unit`.
+    pub calc_time: i64,
+    /// Time unit in which `calc_time` is specified. By default it is seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub calc_time_unit: Option<TimeUnit>,
+    /// Number of sampled pages per each GiB of guest memory.
+    ///
+    /// Default value is 512. For 4KiB guest pages this corresponds to
+    /// sampling ratio of 0.2%. This argument is used only in page
+    /// sampling mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_pages: Option<u64>,
+    /// Mechanism for tracking dirty pages. Default value is 'page-sampling'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<DirtyRateMeasureMode>,
+}
+
+impl QmpCommand for CalcDirtyRateParams {
+    type Return = Empty;
+    const NAME: &'static str = "calc-dirty-rate";
+}
+
+/// Start measuring dirty page rate of the VM.
+///
+/// Results can be retrieved with 'query-dirty-rate' after measurements
+/// are completed.
+#[inline]
+pub fn calc_dirty_rate(params: CalcDirtyRateParams) -> Command<CalcDirtyRateParams, ()> {
+    Command {
+        execute: "calc-dirty-rate".to_string(),
+        arguments: Some(params),
+        id: None,
+    }
+}
+
+/// Marker of 'query-dirty-rate'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryDirtyRate;
+
+impl QmpCommand for QueryDirtyRate {
+    type Return = DirtyRateInfo;
+    const NAME: &'static str = "query-dirty-rate";
+}
+
+/// Query results of the most recent invocation of 'calc-dirty-rate'.
+///
+/// The server returns a `DirtyRateInfo`.
+#[inline]
+pub fn query_dirty_rate() -> Command<(), ()> {
+    Command {
+        execute: "query-dirty-rate".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Arguments of 'set-vcpu-dirty-limit'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetVcpuDirtyLimitParams {
+    /// Index of a virtual CPU, default is all.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_index: Option<i64>,
+    /// Upper limit of dirty page rate (MB/s) for virtual CPUs.
+    pub dirty_rate: u64,
+}
+
+impl QmpCommand for SetVcpuDirtyLimitParams {
+    type Return = Empty;
+    const NAME: &'static str = "set-vcpu-dirty-limit";
+}
+
+/// Set the upper limit of dirty page rate for virtual CPUs.
+///
+/// Requires KVM with accelerator property "dirty-ring-size" set.
+#[inline]
+pub fn set_vcpu_dirty_limit(
+    cpu_index: Option<i64>,
+    dirty_rate: u64,
+) -> Command<SetVcpuDirtyLimitParams, ()> {
+    Command {
+        execute: "set-vcpu-dirty-limit".to_string(),
+        arguments: Some(SetVcpuDirtyLimitParams {
+            cpu_index,
+            dirty_rate,
+        }),
+        id: None,
+    }
+}
+
+/// Arguments of 'cancel-vcpu-dirty-limit'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CancelVcpuDirtyLimitParams {
+    /// Index of a virtual CPU, default is all.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_index: Option<i64>,
+}
+
+impl QmpCommand for CancelVcpuDirtyLimitParams {
+    type Return = Empty;
+    const NAME: &'static str = "cancel-vcpu-dirty-limit";
+}
+
+/// Cancel the upper limit of dirty page rate for virtual CPUs.
+#[inline]
+pub fn cancel_vcpu_dirty_limit(cpu_index: Option<i64>) -> Command<CancelVcpuDirtyLimitParams, ()> {
+    Command {
+        execute: "cancel-vcpu-dirty-limit".to_string(),
+        arguments: Some(CancelVcpuDirtyLimitParams { cpu_index }),
+        id: None,
+    }
+}
+
+/// Marker of 'query-vcpu-dirty-limit'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryVcpuDirtyLimit;
+
+impl QmpCommand for QueryVcpuDirtyLimit {
+    type Return = Vec<DirtyLimitInfo>;
+    const NAME: &'static str = "query-vcpu-dirty-limit";
+}
+
+/// Return information about virtual CPU dirty page rate limits, if any.
+///
+/// The server returns a list of `DirtyLimitInfo`.
+#[inline]
+pub fn query_vcpu_dirty_limit() -> Command<(), ()> {
+    Command {
+        execute: "query-vcpu-dirty-limit".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Marker of 'migrate-start-postcopy'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MigrateStartPostcopy;
+
+impl QmpCommand for MigrateStartPostcopy {
+    type Return = Empty;
+    const NAME: &'static str = "migrate-start-postcopy";
+}
+
+/// Followup to a migration command to switch the migration to postcopy mode.
+///
+/// The postcopy-ram capability must be set on both source and destination
+/// before the original migration command.
+#[inline]
+pub fn migrate_start_postcopy() -> Command<(), ()> {
+    Command {
+        execute: "migrate-start-postcopy".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Marker of 'migrate-pause'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MigratePause;
+
+impl QmpCommand for MigratePause {
+    type Return = Empty;
+    const NAME: &'static str = "migrate-pause";
+}
+
+impl OobSafe for MigratePause {}
+
+/// Pause a migration. Currently it only supports postcopy.
+///
+/// This command may be sent out-of-band.
+#[inline]
+pub fn migrate_pause() -> Command<(), ()> {
+    Command {
+        execute: "migrate-pause".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Arguments of 'migrate-recover'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MigrateRecoverParams {
+    /// The URI to be used for the recovery of migration stream.
+    pub uri: String,
+}
+
+impl QmpCommand for MigrateRecoverParams {
+    type Return = Empty;
+    const NAME: &'static str = "migrate-recover";
+}
+
+impl OobSafe for MigrateRecoverParams {}
+
+/// Provide a recovery migration stream URI.
+///
+/// It continues a paused incoming postcopy migration, and is typically
+/// sent out-of-band on the destination, as its main thread may be blocked
+/// by the broken migration stream.
+#[inline]
+pub fn migrate_recover(uri: String) -> Command<MigrateRecoverParams, ()> {
+    Command {
+        execute: "migrate-recover".to_string(),
+        arguments: Some(MigrateRecoverParams { uri }),
+        id: None,
+    }
+}
+
+/// Migration capabilities enumeration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum MigrationCapability {
+    /// Migration supports xbzrle (Xor Based Zero Run Length Encoding).
+    Xbzrle,
+    /// Controls whether or not the entire VM memory footprint is
+    /// mlock()'d on demand or all at once. Refer to docs/rdma.txt for usage.
+    RdmaPinAll,
+    /// If enabled, QEMU will automatically throttle down the guest
+    /// to speed up convergence of RAM migration.
+    AutoConverge,
+    /// During storage migration encode blocks of zeroes efficiently.
+    ZeroBlocks,
+    /// Generate events for each migration state change.
+    Events,
+    /// Start executing on the migration target before all of RAM has
+    /// been migrated, pulling the remaining pages along as needed.
+    PostcopyRam,
+    /// If enabled, migration will never end, and the state of the VM
+    /// on the primary side will be migrated continuously to the VM on
+    /// secondary side (COLO).
+    #[serde(rename = "x-colo")]
+    XColo,
+    /// If enabled, QEMU will free the migrated ram pages on the source during postcopy-ram migration.
+    ReleaseRam,
+    /// If enabled, migration will use the return path even for precopy.
+    ReturnPath,
+    /// Pause outgoing migration before serialising device state and
+    /// before disabling block IO.
+    PauseBeforeSwitchover,
+    /// Use more than one fd for migration.
+    Multifd,
+    /// If enabled, QEMU will migrate named dirty bitmaps.
+    DirtyBitmaps,
+    /// Calculate downtime for postcopy live migration.
+    PostcopyBlocktime,
+    /// If enabled, the destination will not activate block devices
+    /// (and thus take locks) immediately at the end of migration.
+    LateBlockActivate,
+    /// If enabled, QEMU will not migrate shared memory that is
+    /// accessible on the destination machine.
+    #[serde(rename = "x-ignore-shared")]
+    XIgnoreShared,
+    /// Send the UUID of the source to allow the destination to ensure
+    /// it is the same.
+    ValidateUuid,
+    /// If enabled, the migration stream will be a snapshot of the VM
+    /// exactly at the point when the migration procedure starts.
+    BackgroundSnapshot,
+    /// Controls behavior on sending memory pages on migration.
+    /// When true, enables a zero-copy mechanism for sending memory pages,
+    /// if host supports it.
+    ZeroCopySend,
+    /// If enabled, the migration process will allow postcopy requests
+    /// to preempt precopy stream.
+    PostcopyPreempt,
+    /// If enabled, migration will not stop the source VM and complete
+    /// the migration until an ACK is received from the destination
+    /// that it's OK to do so.
+    SwitchoverAck,
+    /// If enabled, migration will throttle vCPUs as needed to keep
+    /// their dirty page rate within `vcpu-dirty-limit`.
+    DirtyLimit,
+    /// Migrate using fixed offsets in the migration file for each RAM page.
+    MappedRam,
+}
+
+/// Migration capability information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct MigrationCapabilityStatus {
+    /// Capability enum.
+    pub capability: MigrationCapability,
+    /// Capability state bool.
+    pub state: bool,
+}
+
+/// Arguments of 'migrate-set-capabilities'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MigrateSetCapabilitiesParams {
+    /// JSON array of capability modifications to make.
+    pub capabilities: Vec<MigrationCapabilityStatus>,
+}
+
+impl QmpCommand for MigrateSetCapabilitiesParams {
+    type Return = Empty;
+    const NAME: &'static str = "migrate-set-capabilities";
+}
+
+/// Enable/Disable the following migration capabilities (like xbzrle).
+#[inline]
+pub fn migrate_set_capabilities(
+    capabilities: Vec<MigrationCapabilityStatus>,
+) -> Command<MigrateSetCapabilitiesParams, ()> {
+    Command {
+        execute: "migrate-set-capabilities".to_string(),
+        arguments: Some(MigrateSetCapabilitiesParams { capabilities }),
+        id: None,
+    }
+}
+
+/// Marker of 'query-migrate-capabilities'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryMigrateCapabilities;
+
+impl QmpCommand for QueryMigrateCapabilities {
+    type Return = Vec<MigrationCapabilityStatus>;
+    const NAME: &'static str = "query-migrate-capabilities";
+}
+
+/// Return information about the current migration capabilities status.
+///
+/// The server returns a list of `MigrationCapabilityStatus`.
+#[inline]
+pub fn query_migrate_capabilities() -> Command<(), ()> {
+    Command {
+        execute: "query-migrate-capabilities".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// An enumeration of multifd compression methods.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MultiFdCompression {
+    /// No compression.
+    None,
+    /// Use zlib compression method.
+    Zlib,
+    /// Use zstd compression method.
+    Zstd,
+    /// Use qatzip compression method.
+    Qatzip,
+    /// Use qpl compression method.
+    Qpl,
+    /// Use UADK library compression method.
+    Uadk,
+}
+
+/// An enumeration of migration modes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MigMode {
+    /// The original form of migration.
+    Normal,
+    /// The migrate command stops the VM and saves state to the URI; the
+    /// new QEMU may be started after a host reboot.
+    CprReboot,
+    /// The new QEMU is started on the same host and receives the state,
+    /// keeping guest RAM in place.
+    CprTransfer,
+}
+
+/// An enumeration of zero page detection methods.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ZeroPageDetection {
+    /// Do not perform zero page checking.
+    None,
+    /// Perform zero page checking in the main migration thread.
+    Legacy,
+    /// Perform zero page checking in the multifd sender thread.
+    Multifd,
+}
+
+/// Transformation applied to a bitmap when it is migrated.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct BitmapMigrationBitmapAliasTransform {
+    /// Override the persistence of the bitmap on the destination.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persistent: Option<bool>,
+}
+
+/// Alias of a dirty bitmap during migration.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct BitmapMigrationBitmapAlias {
+    /// The name of the bitmap.
+    pub name: String,
+    /// An alias name for migration.
+    pub alias: String,
+    /// Transformation applied to the bitmap on migration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform: Option<BitmapMigrationBitmapAliasTransform>,
+}
+
+/// Alias of a block node and its bitmaps during migration.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct BitmapMigrationNodeAlias {
+    /// A block node name.
+    pub node_name: String,
+    /// An alias block node name for migration.
+    pub alias: String,
+    /// Mappings for the bitmaps on this node.
+    pub bitmaps: Vec<BitmapMigrationBitmapAlias>,
+}
+
+/// Migration parameters.
+///
+/// Used both as the arguments of 'migrate-set-parameters' and as the return
+/// value of 'query-migrate-parameters'. When setting, only parameters that
+/// are set are changed; the others keep their current values. When
+/// querying, QEMU reports all parameters it supports.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct MigrateParameters {
+    /// Initial percentage of time guest cpus are throttled when migration
+    /// auto-converge is activated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_throttle_initial: Option<u8>,
+    /// Throttle percentage increase each time auto-converge detects that
+    /// migration is not making progress.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_throttle_increment: Option<u8>,
+    /// Maximum speed for migration, in bytes per second.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bandwidth: Option<u64>,
+    /// Set maximum tolerated downtime for migration, in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downtime_limit: Option<u64>,
+    /// Number of channels used to migrate data in parallel.
+    ///
+    /// This is the same number that the number of sockets used for
+    /// migration. The default value is 2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multifd_channels: Option<u8>,
+    /// Background transfer bandwidth during postcopy, in bytes per second.
+    ///
+    /// Defaults to 0 (unlimited).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_postcopy_bandwidth: Option<u64>,
+    /// ID of the 'tls-creds' object that provides credentials for
+    /// establishing a TLS connection over the migration data channel.
+    ///
+    /// An empty string means that QEMU will use plain text mode for
+    /// migration, rather than TLS.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_creds: Option<String>,
+    /// Hostname of the target host for the migration, used to validate
+    /// the server's x509 certificate identity.
+    ///
+    /// If empty, QEMU will use the hostname from the migration URI, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_hostname: Option<String>,
+    /// ID of the 'authz' object subclass that provides access control
+    /// checking of the TLS x509 certificate distinguished name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_authz: Option<String>,
+    /// Initial delay in milliseconds before sending the first announce.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub announce_initial: Option<u64>,
+    /// Maximum delay in milliseconds between packets in the announcement.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub announce_max: Option<u64>,
+    /// Number of self-announce packets sent after migration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub announce_rounds: Option<u64>,
+    /// Increase in delay in milliseconds between subsequent packets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub announce_step: Option<u64>,
+    /// Ratio of dirtied memory to transferred memory at which throttling
+    /// starts, in percent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub throttle_trigger_threshold: Option<u8>,
+    /// Make the cpu throttling slower at the tail stage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_throttle_tailslow: Option<bool>,
+    /// Bandwidth available for the switchover phase, in bytes per second.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avail_switchover_bandwidth: Option<u64>,
+    /// Delay in milliseconds between checkpoints in COLO mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x_checkpoint_delay: Option<u32>,
+    /// Cache size used by XBZRLE, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xbzrle_cache_size: Option<u64>,
+    /// Maximum cpu throttle percentage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_cpu_throttle: Option<u8>,
+    /// Compression method used by multifd.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multifd_compression: Option<MultiFdCompression>,
+    /// Compression level used by multifd zlib compression, from 0 to 9.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multifd_zlib_level: Option<u8>,
+    /// Compression level used by multifd qatzip compression, from 1 to 9.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multifd_qatzip_level: Option<u8>,
+    /// Compression level used by multifd zstd compression, from 0 to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multifd_zstd_level: Option<u8>,
+    /// Maps block nodes and bitmaps on them to aliases for migration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_bitmap_mapping: Option<Vec<BitmapMigrationNodeAlias>>,
+    /// Periodic time in milliseconds of the dirty limit during live
+    /// migration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x_vcpu_dirty_limit_period: Option<u64>,
+    /// Dirty page rate limit of each vCPU, in MB/s.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcpu_dirty_limit: Option<u64>,
+    /// Migration mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<MigMode>,
+    /// Method of zero page detection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zero_page_detection: Option<ZeroPageDetection>,
+    /// Open migration files with O_DIRECT when possible.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direct_io: Option<bool>,
+}
+
+impl QmpCommand for MigrateParameters {
+    type Return = Empty;
+    const NAME: &'static str = "migrate-set-parameters";
+}
+
+/// Set various migration parameters.
+#[inline]
+pub fn migrate_set_parameters(params: MigrateParameters) -> Command<MigrateParameters, ()> {
+    Command {
+        execute: "migrate-set-parameters".to_string(),
+        arguments: Some(params),
+        id: None,
+    }
+}
+
+/// Marker of 'query-migrate-parameters'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryMigrateParameters;
+
+impl QmpCommand for QueryMigrateParameters {
+    type Return = MigrateParameters;
+    const NAME: &'static str = "query-migrate-parameters";
+}
+
+/// Returns information about the current migration parameters.
+#[inline]
+pub fn query_migrate_parameters() -> Command<(), ()> {
+    Command {
+        execute: "query-migrate-parameters".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+impl MigrationCapabilityStatus {
+    /// Capabilities and parameters for a postcopy migration.
+    ///
+    /// Enables 'postcopy-ram' and 'events'; once migration is running,
+    /// switch to postcopy with 'migrate-start-postcopy'. Capabilities must
+    /// be set on both the source and the destination.
+    pub fn postcopy_preset() -> (Vec<MigrationCapabilityStatus>, MigrateParameters) {
+        let capabilities = vec![
+            MigrationCapabilityStatus {
+                capability: MigrationCapability::PostcopyRam,
+                state: true,
+            },
+            MigrationCapabilityStatus {
+                capability: MigrationCapability::Events,
+                state: true,
+            },
+        ];
+        (capabilities, MigrateParameters::default())
+    }
+
+    /// Capabilities and parameters for a multifd migration over `channels`
+    /// parallel channels.
+    ///
+    /// Enables 'multifd' and 'events', and sets 'multifd-channels'.
+    pub fn multifd_preset(channels: u8) -> (Vec<MigrationCapabilityStatus>, MigrateParameters) {
+        let capabilities = vec![
+            MigrationCapabilityStatus {
+                capability: MigrationCapability::Multifd,
+                state: true,
+            },
+            MigrationCapabilityStatus {
+                capability: MigrationCapability::Events,
+                state: true,
+            },
+        ];
+        let parameters = MigrateParameters {
+            multifd_channels: Some(channels),
+            ..MigrateParameters::default()
+        };
+        (capabilities, parameters)
+    }
+}
+
+/// An enumeration of migration status.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MigrationStatus {
+    /// No migration has been started.
+    None,
+    /// Migration connection setup is in progress.
+    Setup,
+    /// Cancellation is in progress.
+    Cancelling,
+    /// Migration was cancelled by the user.
+    Cancelled,
+    /// In the process of doing migration.
+    Active,
+    /// Like active, but now in postcopy mode.
+    PostcopyActive,
+    /// During postcopy but paused.
+    PostcopyPaused,
+    /// Setup phase for a postcopy recovery process.
+    PostcopyRecoverSetup,
+    /// Trying to recover from a paused postcopy.
+    PostcopyRecover,
+    /// Migration is finished.
+    Completed,
+    /// Some error occurred during migration.
+    Failed,
+    /// VM is in the process of fault tolerance.
+    Colo,
+    /// Paused before device serialisation.
+    PreSwitchover,
+    /// During device serialisation when pause-before-switchover is enabled.
+    Device,
+    /// Wait for device unplug request by guest OS to be completed.
+    WaitUnplug,
+}
+
+/// Detailed migration status.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct MigrationStats {
+    /// Amount of bytes already transferred to the target VM.
+    pub transferred: u64,
+    /// Amount of bytes remaining to be transferred to the target VM.
+    pub remaining: u64,
+    /// Total amount of bytes involved in the migration process.
+    pub total: u64,
+    /// Number of duplicate (zero) pages.
+    pub duplicate: u64,
+    /// Number of normal pages.
+    pub normal: u64,
+    /// Number of normal bytes sent.
+    pub normal_bytes: u64,
+    /// Number of pages dirtied by second by the guest.
+    pub dirty_pages_rate: u64,
+    /// Throughput in megabits/sec.
+    pub mbps: f64,
+    /// Number of times that dirty ram was synchronized.
+    pub dirty_sync_count: u64,
+    /// The number of page requests received from the destination.
+    pub postcopy_requests: u64,
+    /// The number of bytes per page for the various page-based statistics.
+    pub page_size: u64,
+    /// The number of bytes sent through multifd.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multifd_bytes: Option<u64>,
+    /// The number of memory pages transferred per second.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pages_per_second: Option<u64>,
+    /// The number of bytes sent in the pre-copy phase.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precopy_bytes: Option<u64>,
+    /// The number of bytes sent while the guest is paused.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downtime_bytes: Option<u64>,
+    /// The number of bytes sent during the post-copy phase.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub postcopy_bytes: Option<u64>,
+    /// Number of times dirty RAM synchronization could not avoid copying
+    /// dirty pages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dirty_sync_missed_zero_copy: Option<u64>,
+    /// Number of skipped zero pages, reported by QEMU releases before 9.1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<u64>,
+}
+
+/// Detailed XBZRLE migration cache statistics.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct XbzrleCacheStats {
+    /// XBZRLE cache size.
+    pub cache_size: u64,
+    /// Amount of bytes already transferred to the target VM.
+    pub bytes: u64,
+    /// Amount of pages transferred to the target VM.
+    pub pages: u64,
+    /// Number of cache miss.
+    pub cache_miss: u64,
+    /// Rate of cache miss.
+    pub cache_miss_rate: f64,
+    /// Rate of encoded bytes.
+    pub encoding_rate: f64,
+    /// Number of overflows.
+    pub overflow: u64,
+}
+
+/// Detailed VFIO devices migration statistics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct VfioStats {
+    /// Amount of bytes transferred to the target VM by VFIO devices.
+    pub transferred: u64,
+}
+
+/// Information about current migration process.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct MigrationInfo {
+    /// Status of the migration process, absent if no migration was started.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<MigrationStatus>,
+    /// Information about RAM migration, present once migration is active.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ram: Option<MigrationStats>,
+    /// Information about disk migration, present only if block migration
+    /// is active.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk: Option<MigrationStats>,
+    /// Information about VFIO device migration, present only with VFIO
+    /// devices being migrated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vfio: Option<VfioStats>,
+    /// Information about the XBZRLE cache, present only if the 'xbzrle'
+    /// capability is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xbzrle_cache: Option<XbzrleCacheStats>,
+    /// Total amount of milliseconds since migration started.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_time: Option<u64>,
+    /// Only present while migration is active; expected downtime in
+    /// milliseconds for the guest in the last walk of the dirty bitmap.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_downtime: Option<u64>,
+    /// Only present when migration finishes correctly; total downtime in
+    /// milliseconds for the guest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downtime: Option<u64>,
+    /// Amount of setup time in milliseconds before the iterations begin.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub setup_time: Option<u64>,
+    /// Percentage of time guest cpus are being throttled during
+    /// auto-converge.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_throttle_percentage: Option<u64>,
+    /// The human readable error description string, when status is 'failed'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_desc: Option<String>,
+    /// A list of reasons an outgoing migration is blocked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocked_reasons: Option<Vec<String>>,
+    /// Total time when all vCPUs were blocked during postcopy live migration,
+    /// in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub postcopy_blocktime: Option<u32>,
+    /// Total time when each vCPU was blocked during postcopy live migration,
+    /// in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub postcopy_vcpu_blocktime: Option<Vec<u32>>,
+    /// Only used for tcp, to know what the real port is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socket_address: Option<Vec<SocketAddress>>,
+    /// Maximum throttle time, in microseconds, of virtual CPUs each dirty
+    /// ring full round.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dirty_limit_throttle_time_per_round: Option<u64>,
+    /// Estimated average dirty ring full time, in microseconds, for each
+    /// dirty ring full round.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dirty_limit_ring_full_time: Option<u64>,
+}
+
+impl MigrationInfo {
+    /// Effective RAM throughput in bytes per second since the `prev` sample,
+    /// taken `elapsed` earlier.
+    ///
+    /// Returns `None` if either sample lacks a 'ram' block, if the
+    /// transferred counter went backwards, or if `elapsed` is zero.
+    pub fn throughput_since(&self, prev: &MigrationInfo, elapsed: Duration) -> Option<f64> {
+        let now = self.ram.as_ref()?.transferred;
+        let before = prev.ram.as_ref()?.transferred;
+        let delta = now.checked_sub(before)?;
+        let seconds = elapsed.as_secs_f64();
+        if seconds == 0.0 {
+            return None;
+        }
+        Some(delta as f64 / seconds)
+    }
+}
+
+/// Marker of 'query-migrate'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryMigrate;
+
+impl QmpCommand for QueryMigrate {
+    type Return = MigrationInfo;
+    const NAME: &'static str = "query-migrate";
+}
+
+/// Returns information about current migration process.
+#[inline]
+pub fn query_migrate() -> Command<(), ()> {
+    Command {
+        execute: "query-migrate".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Emitted from the source side of a migration at the start of each pass
+/// (when it syncs the dirty bitmap).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct MigrationPassEvent {
+    /// An incrementing count (starting at 1 on the first pass).
+    pub pass: i64,
+}
+
+/// The result format for 'query-xen-replication-status'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ReplicationStatus {
+    /// True if an error happened, false if replication is normal.
+    pub error: bool,
+    /// The human readable error description string, when `error` is true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+}
+
+/// Marker of 'query-xen-replication-status'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryXenReplicationStatus;
+
+impl QmpCommand for QueryXenReplicationStatus {
+    type Return = ReplicationStatus;
+    const NAME: &'static str = "query-xen-replication-status";
+}
+
+/// Query replication status while the VM is running.
+///
+/// The server returns a `ReplicationStatus`.
+#[inline]
+pub fn query_xen_replication_status() -> Command<(), ()> {
+    Command {
+        execute: "query-xen-replication-status".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// The modes of COLO (COarse-grained LOck-stepping).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColoMode {
+    /// COLO is disabled.
+    None,
+    /// COLO node in primary side.
+    Primary,
+    /// COLO node in secondary side.
+    Secondary,
+}
+
+/// The reason for a COLO exit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColoExitReason {
+    /// Failover has never happened.
+    ///
+    /// This state does not occur in the COLO_EXIT event, and is only
+    /// visible in the result of 'query-colo-status'.
+    None,
+    /// COLO exit is due to an external request.
+    Request,
+    /// COLO exit is due to an internal error.
+    Error,
+    /// COLO is currently handling a failover (since 4.0).
+    Processing,
+}
+
+/// The result format for 'query-colo-status'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct ColoStatus {
+    /// COLO running mode.
+    ///
+    /// If COLO is running, this field will return 'primary' or 'secondary'.
+    pub mode: ColoMode,
+    /// COLO last running mode.
+    ///
+    /// If COLO is running, this field will return same like mode field,
+    /// after failover we can use this field to get last colo mode.
+    pub last_mode: ColoMode,
+    /// Describes the reason for the COLO exit.
+    pub reason: ColoExitReason,
+}
+
+/// Marker of 'query-colo-status'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryColoStatus;
+
+impl QmpCommand for QueryColoStatus {
+    type Return = ColoStatus;
+    const NAME: &'static str = "query-colo-status";
+}
+
+/// Query COLO status while the VM is running.
+///
+/// The server returns a `ColoStatus`.
+#[inline]
+pub fn query_colo_status() -> Command<(), ()> {
+    Command {
+        execute: "query-colo-status".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Marker of 'x-colo-lost-heartbeat'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct XColoLostHeartbeat;
+
+impl QmpCommand for XColoLostHeartbeat {
+    type Return = Empty;
+    const NAME: &'static str = "x-colo-lost-heartbeat";
+}
+
+/// Tell QEMU that heartbeat is lost, request it to do takeover procedures.
+///
+/// If this command is sent to the PVM, the Primary side will exit COLO mode.
+/// If sent to the Secondary, the Secondary side will run failover work,
+/// then takes over server operation to become the service VM.
+#[inline]
+pub fn x_colo_lost_heartbeat() -> Command<(), ()> {
+    Command {
+        execute: "x-colo-lost-heartbeat".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Arguments of 'snapshot-save'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotSaveParams {
+    /// Identifier for the newly created job.
+    pub job_id: String,
+    /// Name of the snapshot to create.
+    pub tag: String,
+    /// Block device node name to save vmstate to.
+    pub vmstate: String,
+    /// List of block device node names to save a snapshot to.
+    pub devices: Vec<String>,
+}
+
+impl QmpCommand for SnapshotSaveParams {
+    type Return = Empty;
+    const NAME: &'static str = "snapshot-save";
+}
+
+/// Save a VM snapshot.
+///
+/// The snapshot is taken in a background job; the command returns once the
+/// job is started, and its progress is reported through job events.
+#[inline]
+pub fn snapshot_save(params: SnapshotSaveParams) -> Command<SnapshotSaveParams, ()> {
+    Command {
+        execute: "snapshot-save".to_string(),
+        arguments: Some(params),
+        id: None,
+    }
+}
+
+/// Arguments of 'snapshot-load'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotLoadParams {
+    /// Identifier for the newly created job.
+    pub job_id: String,
+    /// Name of the snapshot to load.
+    pub tag: String,
+    /// Block device node name to load vmstate from.
+    pub vmstate: String,
+    /// List of block device node names to load a snapshot from.
+    pub devices: Vec<String>,
+}
+
+impl QmpCommand for SnapshotLoadParams {
+    type Return = Empty;
+    const NAME: &'static str = "snapshot-load";
+}
+
+/// Load a VM snapshot.
+///
+/// Like 'snapshot-save', the snapshot is loaded in a background job.
+#[inline]
+pub fn snapshot_load(params: SnapshotLoadParams) -> Command<SnapshotLoadParams, ()> {
+    Command {
+        execute: "snapshot-load".to_string(),
+        arguments: Some(params),
+        id: None,
+    }
+}
+
+/// Arguments of 'snapshot-delete'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotDeleteParams {
+    /// Identifier for the newly created job.
+    pub job_id: String,
+    /// Name of the snapshot to delete.
+    pub tag: String,
+    /// List of block device node names to delete a snapshot from.
+    pub devices: Vec<String>,
+}
+
+impl QmpCommand for SnapshotDeleteParams {
+    type Return = Empty;
+    const NAME: &'static str = "snapshot-delete";
+}
+
+/// Delete a VM snapshot.
+///
+/// Like 'snapshot-save', the snapshot is deleted in a background job.
+#[inline]
+pub fn snapshot_delete(params: SnapshotDeleteParams) -> Command<SnapshotDeleteParams, ()> {
+    Command {
+        execute: "snapshot-delete".to_string(),
+        arguments: Some(params),
+        id: None,
+    }
+}
+
+/// Arguments of 'xen-save-devices-state'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct XenSaveDevicesStateParams {
+    /// The file to save the state of the devices to as binary data.
+    pub filename: String,
+    /// Optional argument to ask QEMU to treat this command as part of a live
+    /// migration. Default to true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub live: Option<bool>,
+}
+
+impl QmpCommand for XenSaveDevicesStateParams {
+    type Return = Empty;
+    const NAME: &'static str = "xen-save-devices-state";
+}
+
+/// Save the state of all devices to file.
+///
+/// The RAM and the block devices of the VM are not saved by this command.
+#[inline]
+pub fn xen_save_devices_state(
+    filename: String,
+    live: Option<bool>,
+) -> Command<XenSaveDevicesStateParams, ()> {
+    Command {
+        execute: "xen-save-devices-state".to_string(),
+        arguments: Some(XenSaveDevicesStateParams { filename, live }),
+        id: None,
+    }
+}
+
+/// Arguments of 'xen-load-devices-state'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct XenLoadDevicesStateParams {
+    /// The file to load the state of the devices from as binary data.
+    pub filename: String,
+}
+
+impl QmpCommand for XenLoadDevicesStateParams {
+    type Return = Empty;
+    const NAME: &'static str = "xen-load-devices-state";
+}
+
+/// Load the state of all devices from file.
+///
+/// The RAM and the block devices of the VM are not loaded by this command.
+#[inline]
+pub fn xen_load_devices_state(filename: String) -> Command<XenLoadDevicesStateParams, ()> {
+    Command {
+        execute: "xen-load-devices-state".to_string(),
+        arguments: Some(XenLoadDevicesStateParams { filename }),
+        id: None,
+    }
+}
+
+/// Emitted from source side of a migration when migration state is WAIT_UNPLUG.
+///
+/// Device was unplugged by guest operating system. Device resources in QEMU
+/// are kept on standby to be able to re-plug it in case of migration failure.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct UnplugPrimaryEvent {
+    /// QEMU device id of the unplugged device.
+    pub device_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CalcDirtyRateParams, ColoExitReason, ColoMode, ColoStatus, DirtyLimitInfo, DirtyRateInfo,
+        DirtyRateMeasureMode, DirtyRateStatus, MigMode, MigrateParameters, MigrationCapability,
+        MigrationCapabilityStatus, MigrationInfo, MigrationStatus, QueryDirtyRate,
+        QueryVcpuDirtyLimit, ReplicationStatus, SetVcpuDirtyLimitParams, SnapshotDeleteParams,
+        SnapshotSaveParams, TimeUnit, ZeroPageDetection, calc_dirty_rate, cancel_vcpu_dirty_limit,
+        migrate_incoming, migrate_pause, migrate_recover, migrate_set_capabilities,
+        migrate_set_parameters, migrate_start_postcopy, query_dirty_rate, query_migrate,
+        query_migrate_capabilities, query_migrate_parameters, query_vcpu_dirty_limit,
+        query_xen_replication_status, set_vcpu_dirty_limit, snapshot_delete, snapshot_save,
+        x_colo_lost_heartbeat, xen_load_devices_state, xen_save_devices_state,
+    };
+    use crate::generic::QmpCommand;
+    use alloc::{string::ToString, vec, vec::Vec};
+    use core::time::Duration;
+
+    #[test]
+    fn command_names() {
+        assert_eq!(QueryDirtyRate::NAME, query_dirty_rate().execute);
+        assert_eq!(QueryVcpuDirtyLimit::NAME, query_vcpu_dirty_limit().execute);
+        assert_eq!(
+            SetVcpuDirtyLimitParams::NAME,
+            set_vcpu_dirty_limit(None, 1).execute
+        );
+    }
+
+    #[test]
+    fn new_migrate_incoming() {
+        let cmd = migrate_incoming("tcp:0:4444".to_string(), None);
+        let compact_string = r#"{"execute":"migrate-incoming","arguments":{"uri":"tcp:0:4444"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_calc_dirty_rate() {
+        let cmd = calc_dirty_rate(CalcDirtyRateParams {
+            calc_time: 1,
+            calc_time_unit: None,
+            sample_pages: Some(512),
+            mode: None,
+        });
+        let compact_string =
+            r#"{"execute":"calc-dirty-rate","arguments":{"calc-time":1,"sample-pages":512}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let cmd = calc_dirty_rate(CalcDirtyRateParams {
+            calc_time: 500,
+            calc_time_unit: Some(TimeUnit::Millisecond),
+            sample_pages: None,
+            mode: Some(DirtyRateMeasureMode::DirtyRing),
+        });
+        let compact_string = r#"{"execute":"calc-dirty-rate","arguments":{"calc-time":500,"calc-time-unit":"millisecond","mode":"dirty-ring"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_dirty_rate_result() {
+        let cmd = query_dirty_rate();
+        let compact_string = r#"{"execute":"query-dirty-rate"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"{
+            "status": "measuring",
+            "sample-pages": 512,
+            "mode": "page-sampling",
+            "start-time": 1693900454,
+            "calc-time": 10,
+            "calc-time-unit": "second"
+        }"#;
+        let value = DirtyRateInfo {
+            dirty_rate: None,
+            status: DirtyRateStatus::Measuring,
+            start_time: 1693900454,
+            calc_time: 10,
+            calc_time_unit: Some(TimeUnit::Second),
+            sample_pages: 512,
+            mode: DirtyRateMeasureMode::PageSampling,
+            vcpu_dirty_rate: None,
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn new_set_vcpu_dirty_limit() {
+        let cmd = set_vcpu_dirty_limit(Some(1), 200);
+        let compact_string =
+            r#"{"execute":"set-vcpu-dirty-limit","arguments":{"cpu-index":1,"dirty-rate":200}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let cmd = set_vcpu_dirty_limit(None, 200);
+        let compact_string = r#"{"execute":"set-vcpu-dirty-limit","arguments":{"dirty-rate":200}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_cancel_vcpu_dirty_limit() {
+        let cmd = cancel_vcpu_dirty_limit(Some(1));
+        let compact_string = r#"{"execute":"cancel-vcpu-dirty-limit","arguments":{"cpu-index":1}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let cmd = cancel_vcpu_dirty_limit(None);
+        let compact_string = r#"{"execute":"cancel-vcpu-dirty-limit","arguments":{}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_vcpu_dirty_limit_result() {
+        let cmd = query_vcpu_dirty_limit();
+        let compact_string = r#"{"execute":"query-vcpu-dirty-limit"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[
+            {"limit-rate": 60, "current-rate": 3, "cpu-index": 0},
+            {"limit-rate": 60, "current-rate": 3, "cpu-index": 1}
+        ]"#;
+        let value = vec![
+            DirtyLimitInfo {
+                cpu_index: 0,
+                limit_rate: 60,
+                current_rate: 3,
+            },
+            DirtyLimitInfo {
+                cpu_index: 1,
+                limit_rate: 60,
+                current_rate: 3,
+            },
+        ];
+        assert_eq!(value, serde_json::from_str::<Vec<_>>(string).unwrap());
+    }
+
+    #[test]
+    fn new_migrate_start_postcopy() {
+        let cmd = migrate_start_postcopy();
+        let compact_string = r#"{"execute":"migrate-start-postcopy"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_migrate_pause() {
+        let cmd = migrate_pause();
+        let compact_string = r#"{"execute":"migrate-pause"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_migrate_recover() {
+        let cmd = migrate_recover("tcp:192.168.1.200:12345".to_string());
+        let compact_string =
+            r#"{"execute":"migrate-recover","arguments":{"uri":"tcp:192.168.1.200:12345"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_migrate_set_capabilities() {
+        let cmd = migrate_set_capabilities(vec![MigrationCapabilityStatus {
+            capability: MigrationCapability::Xbzrle,
+            state: true,
+        }]);
+        let compact_string = r#"{"execute":"migrate-set-capabilities","arguments":{"capabilities":[{"capability":"xbzrle","state":true}]}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_migrate_capabilities_result() {
+        let cmd = query_migrate_capabilities();
+        let compact_string = r#"{"execute":"query-migrate-capabilities"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[
+            {"state": true, "capability": "xbzrle"},
+            {"state": false, "capability": "x-colo"},
+            {"state": false, "capability": "postcopy-ram"}
+        ]"#;
+        let value = vec![
+            MigrationCapabilityStatus {
+                capability: MigrationCapability::Xbzrle,
+                state: true,
+            },
+            MigrationCapabilityStatus {
+                capability: MigrationCapability::XColo,
+                state: false,
+            },
+            MigrationCapabilityStatus {
+                capability: MigrationCapability::PostcopyRam,
+                state: false,
+            },
+        ];
+        assert_eq!(value, serde_json::from_str::<Vec<_>>(string).unwrap());
+    }
+
+    #[test]
+    fn new_query_xen_replication_status() {
+        let cmd = query_xen_replication_status();
+        let compact_string = r#"{"execute":"query-xen-replication-status"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_xen_replication_status_result() {
+        let string = r#"{"error": true, "desc": "Block device is not ready"}"#;
+        let value = ReplicationStatus {
+            error: true,
+            desc: Some("Block device is not ready".to_string()),
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn new_x_colo_lost_heartbeat() {
+        let cmd = x_colo_lost_heartbeat();
+        let compact_string = r#"{"execute":"x-colo-lost-heartbeat"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_colo_status_result() {
+        let string = r#"{"reason": "request", "mode": "none", "last-mode": "primary"}"#;
+        let value = ColoStatus {
+            mode: ColoMode::None,
+            last_mode: ColoMode::Primary,
+            reason: ColoExitReason::Request,
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn new_snapshot_save() {
+        let cmd = snapshot_save(SnapshotSaveParams {
+            job_id: "snapsave0".to_string(),
+            tag: "my-snap".to_string(),
+            vmstate: "disk0".to_string(),
+            devices: vec!["disk0".to_string(), "disk1".to_string()],
+        });
+        let compact_string = r#"{"execute":"snapshot-save","arguments":{"job-id":"snapsave0","tag":"my-snap","vmstate":"disk0","devices":["disk0","disk1"]}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_snapshot_delete() {
+        let cmd = snapshot_delete(SnapshotDeleteParams {
+            job_id: "snapdelete0".to_string(),
+            tag: "my-snap".to_string(),
+            devices: vec!["disk0".to_string(), "disk1".to_string()],
+        });
+        let compact_string = r#"{"execute":"snapshot-delete","arguments":{"job-id":"snapdelete0","tag":"my-snap","devices":["disk0","disk1"]}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_xen_save_devices_state() {
+        let cmd = xen_save_devices_state("/tmp/save".to_string(), Some(false));
+        let compact_string = r#"{"execute":"xen-save-devices-state","arguments":{"filename":"/tmp/save","live":false}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let cmd = xen_save_devices_state("/tmp/save".to_string(), None);
+        let compact_string =
+            r#"{"execute":"xen-save-devices-state","arguments":{"filename":"/tmp/save"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_xen_load_devices_state() {
+        let cmd = xen_load_devices_state("/tmp/resume".to_string());
+        let compact_string =
+            r#"{"execute":"xen-load-devices-state","arguments":{"filename":"/tmp/resume"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_migrate_set_parameters() {
+        let cmd = migrate_set_parameters(MigrateParameters {
+            max_bandwidth: Some(33554432),
+            downtime_limit: Some(300),
+            ..MigrateParameters::default()
+        });
+        let compact_string = r#"{"execute":"migrate-set-parameters","arguments":{"max-bandwidth":33554432,"downtime-limit":300}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn migration_capability_presets() {
+        let (capabilities, parameters) = MigrationCapabilityStatus::postcopy_preset();
+        for capability in [
+            MigrationCapability::PostcopyRam,
+            MigrationCapability::Events,
+        ] {
+            assert!(capabilities.contains(&MigrationCapabilityStatus {
+                capability,
+                state: true,
+            }));
+        }
+        assert_eq!(parameters, MigrateParameters::default());
+
+        let (capabilities, parameters) = MigrationCapabilityStatus::multifd_preset(4);
+        assert!(capabilities.contains(&MigrationCapabilityStatus {
+            capability: MigrationCapability::Multifd,
+            state: true,
+        }));
+        let cmd = migrate_set_parameters(parameters);
+        let compact_string =
+            r#"{"execute":"migrate-set-parameters","arguments":{"multifd-channels":4}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_migrate_throughput() {
+        let cmd = query_migrate();
+        let compact_string = r#"{"execute":"query-migrate"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let sample = |transferred: u64| {
+            let string = alloc::format!(
+                r#"{{"status": "active", "total-time": 12345, "setup-time": 12,
+                "expected-downtime": 12345, "ram": {{"transferred": {transferred},
+                "remaining": 123, "total": 246, "duplicate": 123, "normal": 123,
+                "normal-bytes": 123456, "dirty-pages-rate": 0, "mbps": 8.5,
+                "dirty-sync-count": 15, "postcopy-requests": 0, "page-size": 4096,
+                "multifd-bytes": 0, "pages-per-second": 30}}}}"#
+            );
+            serde_json::from_str::<MigrationInfo>(&string).unwrap()
+        };
+        let prev = sample(1_000_000);
+        let next = sample(3_000_000);
+        assert_eq!(next.status, Some(MigrationStatus::Active));
+        assert_eq!(next.ram.as_ref().unwrap().page_size, 4096);
+        let rate = next.throughput_since(&prev, Duration::from_millis(500));
+        assert_eq!(rate, Some(4_000_000.0));
+        assert_eq!(prev.throughput_since(&next, Duration::from_secs(1)), None);
+        assert_eq!(next.throughput_since(&prev, Duration::ZERO), None);
+    }
+
+    #[test]
+    fn migration_throughput_without_ram() {
+        let setup: MigrationInfo = serde_json::from_str(r#"{"status": "setup"}"#).unwrap();
+        let none: MigrationInfo = serde_json::from_str("{}").unwrap();
+        assert_eq!(none.status, None);
+        assert_eq!(setup.throughput_since(&none, Duration::from_secs(1)), None);
+        assert_eq!(setup.throughput_since(&setup, Duration::from_secs(1)), None);
+    }
+
+    #[test]
+    fn migrate_parameters_tls() {
+        let cmd = migrate_set_parameters(MigrateParameters {
+            tls_creds: Some("tls0".to_string()),
+            tls_hostname: Some("dst.example.com".to_string()),
+            ..MigrateParameters::default()
+        });
+        let compact_string = r#"{"execute":"migrate-set-parameters","arguments":{"tls-creds":"tls0","tls-hostname":"dst.example.com"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let cmd = query_migrate_parameters();
+        let compact_string = r#"{"execute":"query-migrate-parameters"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"{"cpu-throttle-initial": 20, "cpu-throttle-increment": 10,
+            "max-bandwidth": 33554432, "downtime-limit": 300, "tls-creds": "tls0",
+            "tls-hostname": "", "tls-authz": "authz0", "announce-initial": 50,
+            "announce-max": 550, "announce-rounds": 5, "announce-step": 100,
+            "throttle-trigger-threshold": 50, "cpu-throttle-tailslow": false,
+            "avail-switchover-bandwidth": 0, "x-checkpoint-delay": 20000,
+            "multifd-channels": 2, "xbzrle-cache-size": 67108864,
+            "max-postcopy-bandwidth": 0, "max-cpu-throttle": 99,
+            "multifd-compression": "none", "multifd-zlib-level": 1,
+            "multifd-qatzip-level": 1, "multifd-zstd-level": 1,
+            "block-bitmap-mapping": [{"node-name": "disk0", "alias": "d0",
+            "bitmaps": [{"name": "bitmap0", "alias": "b0",
+            "transform": {"persistent": true}}]}],
+            "x-vcpu-dirty-limit-period": 1000, "vcpu-dirty-limit": 1,
+            "mode": "normal", "zero-page-detection": "multifd", "direct-io": false}"#;
+        let parameters: MigrateParameters = serde_json::from_str(string).unwrap();
+        assert_eq!(parameters.mode, Some(MigMode::Normal));
+        assert_eq!(
+            parameters.zero_page_detection,
+            Some(ZeroPageDetection::Multifd)
+        );
+        assert_eq!(
+            parameters.block_bitmap_mapping.unwrap()[0].bitmaps[0].alias,
+            "b0"
+        );
+        assert_eq!(parameters.tls_creds.as_deref(), Some("tls0"));
+        assert_eq!(parameters.tls_hostname.as_deref(), Some(""));
+        assert_eq!(parameters.tls_authz.as_deref(), Some("authz0"));
+    }
+
+    #[test]
+    fn query_migrate_completed_result() {
+        let string = r#"{"status": "completed", "total-time": 12345, "setup-time": 12,
+            "downtime": 12345, "blocked-reasons": [],
+            "socket-address": [{"type": "inet", "host": "0.0.0.0", "port": "4446"}],
+            "ram": {"transferred": 123, "remaining": 0, "total": 246, "duplicate": 123,
+            "normal": 123, "normal-bytes": 123456, "dirty-pages-rate": 0, "mbps": 0,
+            "dirty-sync-count": 15, "postcopy-requests": 0, "page-size": 4096,
+            "multifd-bytes": 0, "pages-per-second": 0, "precopy-bytes": 123,
+            "downtime-bytes": 0, "postcopy-bytes": 0, "dirty-sync-missed-zero-copy": 0},
+            "xbzrle-cache": {"cache-size": 67108864, "bytes": 20971520, "pages": 2444343,
+            "cache-miss": 2244, "cache-miss-rate": 0.123, "encoding-rate": 80.1,
+            "overflow": 34434}}"#;
+        let info: MigrationInfo = serde_json::from_str(string).unwrap();
+        assert_eq!(info.status, Some(MigrationStatus::Completed));
+        assert_eq!(info.xbzrle_cache.unwrap().overflow, 34434);
+        assert_eq!(info.socket_address.as_ref().unwrap().len(), 1);
+        assert_eq!(info.blocked_reasons, Some(vec![]));
+    }
+}
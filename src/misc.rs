@@ -0,0 +1,631 @@
+//! Miscellanea.
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::generic::{Command, Empty, QmpCommand};
+
+/// Information about an iothread.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct IoThreadInfo {
+    /// The identifier of the iothread.
+    pub id: String,
+    /// ID of the underlying host thread.
+    pub thread_id: i64,
+    /// Maximum polling time in ns, 0 means polling is disabled.
+    pub poll_max_ns: i64,
+    /// How many ns will be added to polling time, 0 means that it's not configured.
+    pub poll_grow: i64,
+    /// How many ns will be removed from polling time, 0 means that it's not configured.
+    pub poll_shrink: i64,
+    /// Maximum number of requests in a batch for the AIO engine,
+    /// 0 means that the engine will use its default.
+    pub aio_max_batch: i64,
+}
+
+/// Marker of 'query-iothreads'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryIothreads;
+
+impl QmpCommand for QueryIothreads {
+    type Return = Vec<IoThreadInfo>;
+    const NAME: &'static str = "query-iothreads";
+}
+
+/// Returns a list of information about each iothread.
+///
+/// Note: this list excludes the QEMU main loop thread, which is not declared
+/// using the -object iothread command-line option. It is always the main thread
+/// of the process.
+#[inline]
+pub fn query_iothreads() -> Command<(), ()> {
+    Command {
+        execute: "query-iothreads".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Information about a file descriptor that was added to an fd set.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct FdsetFdInfo {
+    /// The file descriptor value.
+    pub fd: i64,
+    /// A free-form string that can be used to describe the fd.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opaque: Option<String>,
+}
+
+/// Information about an fd set.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct FdsetInfo {
+    /// The ID of the fd set.
+    pub fdset_id: i64,
+    /// A list of file descriptors that belong to this fd set.
+    pub fds: Vec<FdsetFdInfo>,
+}
+
+/// Marker of 'query-fdsets'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryFdsets;
+
+impl QmpCommand for QueryFdsets {
+    type Return = Vec<FdsetInfo>;
+    const NAME: &'static str = "query-fdsets";
+}
+
+/// Return information describing all fd sets.
+///
+/// Note: the list of fd sets is shared by all monitor connections.
+#[inline]
+pub fn query_fdsets() -> Command<(), ()> {
+    Command {
+        execute: "query-fdsets".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Information about an SGX EPC section.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct SgxEpcSection {
+    /// The NUMA node.
+    pub node: i64,
+    /// The size of the EPC section.
+    pub size: u64,
+}
+
+/// Information about Intel Software Guard eXtensions (SGX) support.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct SgxInfo {
+    /// True if SGX is supported.
+    pub sgx: bool,
+    /// True if SGX1 is supported.
+    pub sgx1: bool,
+    /// True if SGX2 is supported.
+    pub sgx2: bool,
+    /// True if FLC is supported.
+    pub flc: bool,
+    /// The EPC size in bytes.
+    ///
+    /// Deprecated: reported by QEMU before 8.0 only, use `sections` instead.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section_size: Option<u64>,
+    /// The EPC sections information.
+    pub sections: Vec<SgxEpcSection>,
+}
+
+/// Marker of 'query-sgx'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QuerySgx;
+
+impl QmpCommand for QuerySgx {
+    type Return = SgxInfo;
+    const NAME: &'static str = "query-sgx";
+}
+
+/// Returns information about configured SGX capabilities of guest.
+#[inline]
+pub fn query_sgx() -> Command<(), ()> {
+    Command {
+        execute: "query-sgx".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Marker of 'query-sgx-capabilities'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QuerySgxCapabilities;
+
+impl QmpCommand for QuerySgxCapabilities {
+    type Return = SgxInfo;
+    const NAME: &'static str = "query-sgx-capabilities";
+}
+
+/// Returns information about SGX capabilities of host.
+#[inline]
+pub fn query_sgx_capabilities() -> Command<(), ()> {
+    Command {
+        execute: "query-sgx-capabilities".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Emitted when the guest changes the RTC time.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct RtcChangeEvent {
+    /// Offset in seconds between base RTC clock (as specified by -rtc base),
+    /// and new RTC clock value.
+    pub offset: i64,
+    /// Path to the RTC object in the QOM tree.
+    pub qom_path: String,
+}
+
+/// Marker of 'stop'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Stop;
+
+impl QmpCommand for Stop {
+    type Return = Empty;
+    const NAME: &'static str = "stop";
+}
+
+/// Stop all guest VCPU execution.
+///
+/// This function will succeed even if the guest is already in the stopped
+/// state. The server emits a 'STOP' event once the guest is stopped.
+#[inline]
+pub fn stop() -> Command<(), ()> {
+    Command {
+        execute: "stop".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Marker of 'cont'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Cont;
+
+impl QmpCommand for Cont {
+    type Return = Empty;
+    const NAME: &'static str = "cont";
+}
+
+/// Resume guest VCPU execution.
+///
+/// This command will succeed if the guest is currently running. The server
+/// emits a 'RESUME' event once the guest is running.
+#[inline]
+pub fn cont() -> Command<(), ()> {
+    Command {
+        execute: "cont".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Marker of 'quit'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Quit;
+
+impl QmpCommand for Quit {
+    type Return = Empty;
+    const NAME: &'static str = "quit";
+}
+
+/// This command will cause the QEMU process to exit gracefully.
+///
+/// While every attempt is made to send the QMP response before terminating,
+/// this is not guaranteed. When using this interface, a premature EOF would
+/// not be unexpected.
+#[inline]
+pub fn quit() -> Command<(), ()> {
+    Command {
+        execute: "quit".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Arguments of 'human-monitor-command'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HumanMonitorCommandParams {
+    /// The command to execute in the human monitor.
+    pub command_line: String,
+    /// The default CPU to use for commands that require an implicit CPU.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_index: Option<i64>,
+}
+
+impl QmpCommand for HumanMonitorCommandParams {
+    type Return = String;
+    const NAME: &'static str = "human-monitor-command";
+}
+
+/// Execute a command on the human monitor and return the output.
+///
+/// The human monitor's output is not guaranteed to be stable; use
+/// `parse_hmp_lines` to split the returned string into lines.
+#[inline]
+pub fn human_monitor_command(
+    command_line: String,
+    cpu_index: Option<i64>,
+) -> Command<HumanMonitorCommandParams, ()> {
+    Command {
+        execute: "human-monitor-command".to_string(),
+        arguments: Some(HumanMonitorCommandParams {
+            command_line,
+            cpu_index,
+        }),
+        id: None,
+    }
+}
+
+/// Split the output of 'human-monitor-command' into lines.
+///
+/// Both `\n` and `\r\n` line endings are accepted; trailing whitespace
+/// is trimmed from each line, and trailing empty lines are dropped.
+pub fn parse_hmp_lines(output: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Possible types for an option parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommandLineParameterType {
+    /// Accepts a character string.
+    String,
+    /// Accepts "on" or "off".
+    Boolean,
+    /// Accepts a number.
+    Number,
+    /// Accepts a number followed by an optional suffix (K)ilo, (M)ega,
+    /// (G)iga, (T)era.
+    Size,
+}
+
+/// Details about a single parameter of a command line option.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct CommandLineParameterInfo {
+    /// Parameter name.
+    pub name: String,
+    /// Parameter type.
+    #[serde(rename = "type")]
+    pub param_type: CommandLineParameterType,
+    /// Human readable text string, not suitable for parsing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+    /// Default value string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+/// A parameter default value, coerced according to its declared type.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ParamValue {
+    /// A character string.
+    String(String),
+    /// A boolean value.
+    Bool(bool),
+    /// A number.
+    Number(u64),
+    /// A size in bytes.
+    Size(u64),
+}
+
+impl CommandLineParameterInfo {
+    /// Coerce the default value string according to the parameter type.
+    ///
+    /// Returns `None` if there is no default value, or if it cannot be
+    /// parsed as the declared type.
+    pub fn typed_default(&self) -> Option<ParamValue> {
+        let default = self.default.as_deref()?;
+        match self.param_type {
+            CommandLineParameterType::String => Some(ParamValue::String(default.to_string())),
+            CommandLineParameterType::Boolean => match default {
+                "on" | "yes" | "true" => Some(ParamValue::Bool(true)),
+                "off" | "no" | "false" => Some(ParamValue::Bool(false)),
+                _ => None,
+            },
+            CommandLineParameterType::Number => parse_number(default).map(ParamValue::Number),
+            CommandLineParameterType::Size => parse_size(default).map(ParamValue::Size),
+        }
+    }
+}
+
+fn parse_number(s: &str) -> Option<u64> {
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+fn parse_size(s: &str) -> Option<u64> {
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, suffix) = s.split_at(split);
+    let shift = match suffix {
+        "" | "b" | "B" => 0,
+        "k" | "K" => 10,
+        "m" | "M" => 20,
+        "g" | "G" => 30,
+        "t" | "T" => 40,
+        "p" | "P" => 50,
+        "e" | "E" => 60,
+        _ => return None,
+    };
+    number.parse::<u64>().ok()?.checked_mul(1 << shift)
+}
+
+/// Details about a command line option, including its list of parameter details.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct CommandLineOptionInfo {
+    /// Option name.
+    pub option: String,
+    /// An array of `CommandLineParameterInfo`.
+    pub parameters: Vec<CommandLineParameterInfo>,
+}
+
+/// Arguments of 'query-command-line-options'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct QueryCommandLineOptionsParams {
+    /// Option name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub option: Option<String>,
+}
+
+impl QmpCommand for QueryCommandLineOptionsParams {
+    type Return = Vec<CommandLineOptionInfo>;
+    const NAME: &'static str = "query-command-line-options";
+}
+
+/// Query command line option schema.
+///
+/// Returns all command line options, or only the one named by `option`.
+#[inline]
+pub fn query_command_line_options(
+    option: Option<String>,
+) -> Command<QueryCommandLineOptionsParams, ()> {
+    Command {
+        execute: "query-command-line-options".to_string(),
+        arguments: Some(QueryCommandLineOptionsParams { option }),
+        id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CommandLineOptionInfo, CommandLineParameterInfo, CommandLineParameterType, FdsetFdInfo,
+        FdsetInfo, IoThreadInfo, ParamValue, SgxEpcSection, SgxInfo, cont, human_monitor_command,
+        parse_hmp_lines, query_command_line_options, query_fdsets, query_iothreads, query_sgx,
+        query_sgx_capabilities, quit, stop,
+    };
+    use alloc::{string::ToString, vec, vec::Vec};
+
+    #[test]
+    fn query_iothreads_result() {
+        let cmd = query_iothreads();
+        let compact_string = r#"{"execute":"query-iothreads"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[
+            {
+                "id": "iothread0",
+                "thread-id": 3134,
+                "poll-max-ns": 32768,
+                "poll-grow": 0,
+                "poll-shrink": 0,
+                "aio-max-batch": 0
+            }
+        ]"#;
+        let value = vec![IoThreadInfo {
+            id: "iothread0".to_string(),
+            thread_id: 3134,
+            poll_max_ns: 32768,
+            poll_grow: 0,
+            poll_shrink: 0,
+            aio_max_batch: 0,
+        }];
+        assert_eq!(value, serde_json::from_str::<Vec<_>>(string).unwrap());
+    }
+
+    #[test]
+    fn query_fdsets_result() {
+        let cmd = query_fdsets();
+        let compact_string = r#"{"execute":"query-fdsets"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[
+            {
+                "fds": [
+                    {"fd": 30, "opaque": "rdonly:/path/to/file"},
+                    {"fd": 24}
+                ],
+                "fdset-id": 1
+            }
+        ]"#;
+        let value = vec![FdsetInfo {
+            fdset_id: 1,
+            fds: vec![
+                FdsetFdInfo {
+                    fd: 30,
+                    opaque: Some("rdonly:/path/to/file".to_string()),
+                },
+                FdsetFdInfo {
+                    fd: 24,
+                    opaque: None,
+                },
+            ],
+        }];
+        assert_eq!(value, serde_json::from_str::<Vec<_>>(string).unwrap());
+    }
+
+    #[test]
+    fn query_sgx_capabilities_result() {
+        let cmd = query_sgx_capabilities();
+        let compact_string = r#"{"execute":"query-sgx-capabilities"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"{"sgx": true, "sgx1": true, "sgx2": false, "flc": true,
+            "sections": [{"node": 0, "size": 67108864}, {"node": 1, "size": 29360128}]}"#;
+        let value = SgxInfo {
+            sgx: true,
+            sgx1: true,
+            sgx2: false,
+            flc: true,
+            section_size: None,
+            sections: vec![
+                SgxEpcSection {
+                    node: 0,
+                    size: 67108864,
+                },
+                SgxEpcSection {
+                    node: 1,
+                    size: 29360128,
+                },
+            ],
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn new_query_sgx() {
+        let cmd = query_sgx();
+        let compact_string = r#"{"execute":"query-sgx"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_human_monitor_command() {
+        let cmd = human_monitor_command("info registers".to_string(), Some(0));
+        let compact_string = r#"{"execute":"human-monitor-command","arguments":{"command-line":"info registers","cpu-index":0}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn parse_hmp_lines_endings() {
+        let output = "pc       0000000080000000\r\nmhartid  0000000000000000\r\n\r\n";
+        assert_eq!(
+            parse_hmp_lines(output),
+            ["pc       0000000080000000", "mhartid  0000000000000000"]
+        );
+        let output = "  kvm support: enabled\n\n  kvm: disabled\n\n";
+        assert_eq!(
+            parse_hmp_lines(output),
+            ["  kvm support: enabled", "", "  kvm: disabled"]
+        );
+        assert!(parse_hmp_lines("").is_empty());
+    }
+
+    #[test]
+    fn query_command_line_options_result() {
+        let cmd = query_command_line_options(Some("option-rom".to_string()));
+        let compact_string =
+            r#"{"execute":"query-command-line-options","arguments":{"option":"option-rom"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[
+            {
+                "parameters": [
+                    {"name": "romfile", "type": "string"},
+                    {"name": "bootindex", "type": "number"}
+                ],
+                "option": "option-rom"
+            }
+        ]"#;
+        let value = vec![CommandLineOptionInfo {
+            option: "option-rom".to_string(),
+            parameters: vec![
+                CommandLineParameterInfo {
+                    name: "romfile".to_string(),
+                    param_type: CommandLineParameterType::String,
+                    help: None,
+                    default: None,
+                },
+                CommandLineParameterInfo {
+                    name: "bootindex".to_string(),
+                    param_type: CommandLineParameterType::Number,
+                    help: None,
+                    default: None,
+                },
+            ],
+        }];
+        assert_eq!(
+            value,
+            serde_json::from_str::<Vec<CommandLineOptionInfo>>(string).unwrap()
+        );
+    }
+
+    #[test]
+    fn command_line_parameter_typed_default() {
+        let param = |param_type, default: &str| CommandLineParameterInfo {
+            name: "param".to_string(),
+            param_type,
+            help: None,
+            default: Some(default.to_string()),
+        };
+        assert_eq!(
+            param(CommandLineParameterType::Boolean, "on").typed_default(),
+            Some(ParamValue::Bool(true))
+        );
+        assert_eq!(
+            param(CommandLineParameterType::Boolean, "off").typed_default(),
+            Some(ParamValue::Bool(false))
+        );
+        assert_eq!(
+            param(CommandLineParameterType::Size, "1G").typed_default(),
+            Some(ParamValue::Size(1 << 30))
+        );
+        assert_eq!(
+            param(CommandLineParameterType::Size, "4096").typed_default(),
+            Some(ParamValue::Size(4096))
+        );
+        assert_eq!(
+            param(CommandLineParameterType::Number, "0x10").typed_default(),
+            Some(ParamValue::Number(16))
+        );
+        assert_eq!(
+            param(CommandLineParameterType::String, "hello").typed_default(),
+            Some(ParamValue::String("hello".to_string()))
+        );
+        assert_eq!(
+            param(CommandLineParameterType::Size, "1Q").typed_default(),
+            None
+        );
+        let mut no_default = param(CommandLineParameterType::String, "");
+        no_default.default = None;
+        assert_eq!(no_default.typed_default(), None);
+    }
+
+    #[test]
+    fn new_stop_cont() {
+        let compact_string = r#"{"execute":"stop"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&stop()).unwrap());
+        let compact_string = r#"{"execute":"cont"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cont()).unwrap());
+    }
+
+    #[test]
+    fn new_quit() {
+        let compact_string = r#"{"execute":"quit"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&quit()).unwrap());
+    }
+}
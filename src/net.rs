@@ -0,0 +1,15 @@
+//! Net devices.
+use alloc::string::String;
+use serde::{Deserialize, Serialize};
+
+/// Emitted when VIRTIO_NET_F_STANDBY was enabled during feature negotiation.
+///
+/// Failover primary devices which were hidden (not hotplugged when
+/// requested) before will now be hotplugged by the virtio-net standby device.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct FailoverNegotiatedEvent {
+    /// QEMU device id of the unplugged device.
+    pub device_id: String,
+}
@@ -0,0 +1,46 @@
+//! PCI devices.
+use alloc::{borrow::Cow, format, string::String};
+use serde::{Deserialize, Serialize};
+
+/// Information about the Class of a PCI device.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct PciDeviceClass {
+    /// The class code of the device.
+    pub class: u16,
+    /// A string description of the device's class (not stable, and should
+    /// only be treated as informational).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+}
+
+impl PciDeviceClass {
+    /// Describe the class of the device for display.
+    ///
+    /// Returns `desc` if the server knows the class, or the class code
+    /// in hexadecimal (e.g. `0x0c03`) otherwise.
+    #[inline]
+    pub fn describe(&self) -> Cow<'_, str> {
+        match &self.desc {
+            Some(desc) => Cow::Borrowed(desc),
+            None => Cow::Owned(format!("{:#06x}", self.class)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PciDeviceClass;
+
+    #[test]
+    fn pci_device_class_describe() {
+        let string = r#"{"desc": "Ethernet controller", "class": 512}"#;
+        let class: PciDeviceClass = serde_json::from_str(string).unwrap();
+        assert_eq!(class.describe(), "Ethernet controller");
+
+        let string = r#"{"class": 3075}"#;
+        let class: PciDeviceClass = serde_json::from_str(string).unwrap();
+        assert_eq!(class.desc, None);
+        assert_eq!(class.describe(), "0x0c03");
+    }
+}
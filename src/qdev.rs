@@ -0,0 +1,14 @@
+//! Device infrastructure (qdev).
+use alloc::string::String;
+use serde::{Deserialize, Serialize};
+
+/// Emitted when a device hot unplug fails due to a guest reported error.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct DeviceUnplugGuestErrorEvent {
+    /// The device's ID if it has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    /// The device's QOM path.
+    pub path: String,
+}
@@ -1,12 +1,24 @@
 //! QMP monitor control.
+use alloc::{
+    collections::BTreeSet,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "json")]
+use serde_json::Value;
 
-use crate::generic::Command;
+use crate::generic::{Command, Empty, QmpCommand};
+#[cfg(feature = "json")]
+use crate::generic::{QmpError, parse_response};
 
 /// Enumeration of capabilities to be advertised during initial client connection.
 ///
 /// Used for agreeing on particular QMP extension behaviors.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub enum QmpCapability {
     /// The QMP server supports "out-of-band" (OOB) command execution.
@@ -18,6 +30,7 @@ pub enum QmpCapability {
 /// QEMU C code does not define the actural integer width of `VersionTriple`
 /// members. Here we use `u64` as a temporary representation.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct VersionTriple {
     /// The major version number.
     pub major: u64,
@@ -40,6 +53,7 @@ impl VersionTriple {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct VersionInfo {
     /// The version of QEMU.
     ///
@@ -81,6 +95,66 @@ pub enum WatchdogAction {
     InjectNmi,
 }
 
+impl WatchdogAction {
+    /// All the defined actions.
+    #[inline]
+    pub const fn all() -> &'static [WatchdogAction] {
+        &[
+            WatchdogAction::Reset,
+            WatchdogAction::Shutdown,
+            WatchdogAction::Poweroff,
+            WatchdogAction::Pause,
+            WatchdogAction::Debug,
+            WatchdogAction::None,
+            WatchdogAction::InjectNmi,
+        ]
+    }
+
+    /// Name of the action, as used on the wire.
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            WatchdogAction::Reset => "reset",
+            WatchdogAction::Shutdown => "shutdown",
+            WatchdogAction::Poweroff => "poweroff",
+            WatchdogAction::Pause => "pause",
+            WatchdogAction::Debug => "debug",
+            WatchdogAction::None => "none",
+            WatchdogAction::InjectNmi => "inject-nmi",
+        }
+    }
+}
+
+impl FromStr for WatchdogAction {
+    type Err = WatchdogActionParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WatchdogAction::all()
+            .iter()
+            .find(|action| action.as_str() == s)
+            .copied()
+            .ok_or_else(|| WatchdogActionParseError {
+                input: s.to_string(),
+            })
+    }
+}
+
+/// Error of parsing a `WatchdogAction` from its name.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WatchdogActionParseError {
+    /// The name that matches no action.
+    pub input: String,
+}
+
+impl fmt::Display for WatchdogActionParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown action `{}`", self.input)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WatchdogActionParseError {}
+
 /// Possible QEMU actions upon guest reboot.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -91,6 +165,23 @@ pub enum RebootAction {
     Shutdown,
 }
 
+impl RebootAction {
+    /// All the defined actions.
+    #[inline]
+    pub const fn all() -> &'static [RebootAction] {
+        &[RebootAction::Reset, RebootAction::Shutdown]
+    }
+
+    /// Name of the action, as used on the wire.
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            RebootAction::Reset => "reset",
+            RebootAction::Shutdown => "shutdown",
+        }
+    }
+}
+
 /// Possible QEMU actions upon guest shutdown.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -101,6 +192,23 @@ pub enum ShutdownAction {
     Pause,
 }
 
+impl ShutdownAction {
+    /// All the defined actions.
+    #[inline]
+    pub const fn all() -> &'static [ShutdownAction] {
+        &[ShutdownAction::Poweroff, ShutdownAction::Pause]
+    }
+
+    /// Name of the action, as used on the wire.
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ShutdownAction::Poweroff => "poweroff",
+            ShutdownAction::Pause => "pause",
+        }
+    }
+}
+
 /// Panic action.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -115,8 +223,103 @@ pub enum PanicAction {
     ExitFailure,
 }
 
+impl PanicAction {
+    /// All the defined actions.
+    #[inline]
+    pub const fn all() -> &'static [PanicAction] {
+        &[
+            PanicAction::None,
+            PanicAction::Pause,
+            PanicAction::Shutdown,
+            PanicAction::ExitFailure,
+        ]
+    }
+
+    /// Name of the action, as used on the wire.
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            PanicAction::None => "none",
+            PanicAction::Pause => "pause",
+            PanicAction::Shutdown => "shutdown",
+            PanicAction::ExitFailure => "exit-failure",
+        }
+    }
+}
+
+/// Information about a QMP command.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct CommandInfo {
+    /// The command name.
+    pub name: String,
+}
+
+/// Set of the command names supported by a server, built from the
+/// result of 'query-commands'.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CommandInfoSet {
+    names: BTreeSet<String>,
+}
+
+impl CommandInfoSet {
+    /// Check if the server supports the command of this name.
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.contains(name)
+    }
+}
+
+impl From<Vec<CommandInfo>> for CommandInfoSet {
+    #[inline]
+    fn from(commands: Vec<CommandInfo>) -> Self {
+        CommandInfoSet {
+            names: commands.into_iter().map(|info| info.name).collect(),
+        }
+    }
+}
+
 // TODO other structures.
 
+/// Arguments of 'qmp_capabilities'.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct QmpCapabilitiesParams {
+    /// An optional list of `QmpCapability` values to enable.
+    ///
+    /// The client must not enable any capability that is not mentioned
+    /// in the QMP greeting message. The default is to enable none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable: Option<Vec<QmpCapability>>,
+}
+
+impl QmpCommand for QmpCapabilitiesParams {
+    type Return = Empty;
+    const NAME: &'static str = "qmp_capabilities";
+}
+
+/// Enable QMP capabilities.
+///
+/// This command is valid exactly when first connecting: it must be issued
+/// before any other command will be accepted, and will fail once the monitor
+/// is accepting other commands.
+#[inline]
+pub fn qmp_capabilities(enable: Option<Vec<QmpCapability>>) -> Command<QmpCapabilitiesParams, ()> {
+    Command {
+        execute: "qmp_capabilities".to_string(),
+        arguments: Some(QmpCapabilitiesParams { enable }),
+        id: None,
+    }
+}
+
+/// Marker of 'query-version'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryVersion;
+
+impl QmpCommand for QueryVersion {
+    type Return = VersionInfo;
+    const NAME: &'static str = "query-version";
+}
+
 /// Return the current version of QEMU.
 #[inline]
 pub fn query_version() -> Command<(), ()> {
@@ -127,6 +330,13 @@ pub fn query_version() -> Command<(), ()> {
     }
 }
 
+/// Extract the `VersionInfo` returned by 'query-version' from its response.
+#[cfg(feature = "json")]
+#[inline]
+pub fn parse_version_response(value: &Value) -> Result<VersionInfo, QmpError> {
+    parse_response::<QueryVersion>(value)
+}
+
 /// Arguments of 'watchdog-set-action'.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct WatchdogSetActionParams {
@@ -134,6 +344,11 @@ pub struct WatchdogSetActionParams {
     pub action: WatchdogAction,
 }
 
+impl QmpCommand for WatchdogSetActionParams {
+    type Return = Empty;
+    const NAME: &'static str = "watchdog-set-action";
+}
+
 /// Set watchdog action.
 #[inline]
 pub fn watchdog_set_action(value: WatchdogAction) -> Command<WatchdogSetActionParams, ()> {
@@ -144,34 +359,195 @@ pub fn watchdog_set_action(value: WatchdogAction) -> Command<WatchdogSetActionPa
     }
 }
 
+/// Set watchdog action from its name, such as `"reset"` or `"inject-nmi"`.
+///
+/// Fails if the name is not one of the actions of `WatchdogAction`.
+#[inline]
+pub fn watchdog_set_action_from_str(
+    s: &str,
+) -> Result<Command<WatchdogSetActionParams, ()>, WatchdogActionParseError> {
+    s.parse().map(watchdog_set_action)
+}
+
 /// Arguments of 'set-action'.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SetActionParams {
     /// `RebootAction` action taken on guest reboot.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub reboot: Option<RebootAction>,
     /// `ShutdownAction` action taken on guest shutdown.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub shutdown: Option<ShutdownAction>,
     /// `PanicAction` action taken on guest panic.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub panic: Option<PanicAction>,
     /// `WatchdogAction` action taken when watchdog timer expires.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub watchdog: Option<WatchdogAction>,
 }
 
+impl SetActionParams {
+    /// Create a builder with no action set.
+    #[inline]
+    pub const fn builder() -> SetActionParamsBuilder {
+        SetActionParamsBuilder {
+            inner: SetActionParams {
+                reboot: None,
+                shutdown: None,
+                panic: None,
+                watchdog: None,
+            },
+        }
+    }
+
+    /// The configured action on guest reboot, if any.
+    #[inline]
+    pub const fn effective_reboot(&self) -> Option<RebootAction> {
+        self.reboot
+    }
+
+    /// The configured action on guest shutdown, if any.
+    #[inline]
+    pub const fn effective_shutdown(&self) -> Option<ShutdownAction> {
+        self.shutdown
+    }
+
+    /// The configured action on guest panic, if any.
+    #[inline]
+    pub const fn effective_panic(&self) -> Option<PanicAction> {
+        self.panic
+    }
+
+    /// The configured action when the watchdog timer expires, if any.
+    #[inline]
+    pub const fn effective_watchdog(&self) -> Option<WatchdogAction> {
+        self.watchdog
+    }
+
+    /// Human readable summary of the configured actions.
+    ///
+    /// Actions that are left unchanged are omitted. Reboot and panic actions
+    /// deferring to the shutdown action are followed by the configured
+    /// shutdown action in parentheses, such as `panic: shutdown (pause)`.
+    pub fn describe(&self) -> String {
+        let then_shutdown = |action: &str, deferred: bool| match (deferred, self.shutdown) {
+            (true, Some(shutdown)) => format!("{action} ({})", shutdown.as_str()),
+            _ => action.to_string(),
+        };
+        let mut parts = Vec::new();
+        if let Some(reboot) = self.reboot {
+            let action = then_shutdown(reboot.as_str(), reboot == RebootAction::Shutdown);
+            parts.push(format!("reboot: {action}"));
+        }
+        if let Some(shutdown) = self.shutdown {
+            parts.push(format!("shutdown: {}", shutdown.as_str()));
+        }
+        if let Some(panic) = self.panic {
+            let action = then_shutdown(panic.as_str(), panic == PanicAction::Shutdown);
+            parts.push(format!("panic: {action}"));
+        }
+        if let Some(watchdog) = self.watchdog {
+            parts.push(format!("watchdog: {}", watchdog.as_str()));
+        }
+        if parts.is_empty() {
+            return "no actions changed".to_string();
+        }
+        parts.join(", ")
+    }
+}
+
+/// Builder of `SetActionParams`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SetActionParamsBuilder {
+    inner: SetActionParams,
+}
+
+impl SetActionParamsBuilder {
+    /// Set the action taken on guest reboot.
+    #[inline]
+    pub const fn reboot(mut self, action: RebootAction) -> Self {
+        self.inner.reboot = Some(action);
+        self
+    }
+
+    /// Set the action taken on guest shutdown.
+    #[inline]
+    pub const fn shutdown(mut self, action: ShutdownAction) -> Self {
+        self.inner.shutdown = Some(action);
+        self
+    }
+
+    /// Set the action taken on guest panic.
+    #[inline]
+    pub const fn panic(mut self, action: PanicAction) -> Self {
+        self.inner.panic = Some(action);
+        self
+    }
+
+    /// Set the action taken when watchdog timer expires.
+    #[inline]
+    pub const fn watchdog(mut self, action: WatchdogAction) -> Self {
+        self.inner.watchdog = Some(action);
+        self
+    }
+
+    /// Build the `set-action` arguments.
+    #[inline]
+    pub const fn build(self) -> SetActionParams {
+        self.inner
+    }
+}
+
+impl QmpCommand for SetActionParams {
+    type Return = Empty;
+    const NAME: &'static str = "set-action";
+}
+
 /// Set the actions that will be taken by the emulator in response to guest events.
 #[inline]
 pub fn set_action(params: SetActionParams) -> Command<SetActionParams, ()> {
     Command {
-        execute: "watchdog-set-action".to_string(),
+        execute: "set-action".to_string(),
         arguments: Some(params),
         id: None,
     }
 }
 
+/// Marker of 'query-commands'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryCommands;
+
+impl QmpCommand for QueryCommands {
+    type Return = Vec<CommandInfo>;
+    const NAME: &'static str = "query-commands";
+}
+
+/// Return a list of supported QMP commands by this server.
+///
+/// The result can be converted into a `CommandInfoSet` for lookups.
+#[inline]
+pub fn query_commands() -> Command<(), ()> {
+    Command {
+        execute: "query-commands".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
 // TODO other constructors.
 
 #[cfg(test)]
 mod tests {
-    use super::{VersionInfo, VersionTriple, query_version};
+    use super::{
+        CommandInfoSet, PanicAction, QmpCapability, QueryCommands, QueryVersion, RebootAction,
+        SetActionParams, ShutdownAction, VersionInfo, VersionTriple, WatchdogAction,
+        WatchdogActionParseError, WatchdogSetActionParams, qmp_capabilities, query_commands,
+        query_version, set_action, watchdog_set_action, watchdog_set_action_from_str,
+    };
+    use crate::generic::QmpCommand;
+    use alloc::{string::ToString, vec};
+    #[cfg(feature = "json")]
+    use {super::parse_version_response, crate::generic::QmpError};
 
     #[test]
     fn struct_version_info() {
@@ -203,6 +579,54 @@ mod tests {
         assert_eq!(compact_string, serde_json::to_string(&value).unwrap());
     }
 
+    #[cfg(feature = "json")]
+    #[test]
+    fn parse_version_response_result() {
+        let response = serde_json::json!({
+            "return": {
+                "qemu": {"major": 8, "minor": 2, "micro": 0},
+                "package": ""
+            },
+            "id": 1
+        });
+        let value = VersionInfo {
+            qemu: VersionTriple::new(8, 2, 0),
+            package: "".to_string(),
+        };
+        assert_eq!(value, parse_version_response(&response).unwrap());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn parse_version_response_error() {
+        let response = serde_json::json!({
+            "error": {
+                "class": "CommandNotFound",
+                "desc": "The command query-version has not been found"
+            }
+        });
+        match parse_version_response(&response) {
+            Err(QmpError::Server(info)) => {
+                assert_eq!(info.class, "CommandNotFound");
+                assert_eq!(
+                    info.description,
+                    "The command query-version has not been found"
+                );
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn new_qmp_capabilities() {
+        let cmd = qmp_capabilities(None);
+        let compact_string = r#"{"execute":"qmp_capabilities","arguments":{}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let cmd = qmp_capabilities(Some(vec![QmpCapability::Oob]));
+        let compact_string = r#"{"execute":"qmp_capabilities","arguments":{"enable":["oob"]}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
     #[test]
     fn new_query_version() {
         let cmd = query_version();
@@ -210,6 +634,135 @@ mod tests {
         assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
     }
 
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn version_info_ignores_unknown_fields() {
+        let string = r#"{"qemu":{"major":8,"minor":2,"micro":0,"x-extra":1},"package":""}"#;
+        let value = VersionInfo {
+            qemu: VersionTriple::new(8, 2, 0),
+            package: "".to_string(),
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "strict")]
+    fn version_info_rejects_unknown_fields() {
+        let string = r#"{"qemu":{"major":8,"minor":2,"micro":0,"x-extra":1},"package":""}"#;
+        assert!(serde_json::from_str::<VersionInfo>(string).is_err());
+    }
+
+    // Only relies on `core` and `alloc`, so it also runs with `--no-default-features`.
+    #[test]
+    fn new_query_version_no_std() {
+        let cmd = query_version();
+        assert_eq!(cmd.execute, "query-version");
+        assert_eq!(cmd.arguments, None);
+        assert_eq!(cmd.id, None);
+    }
+
+    #[test]
+    fn all_actions() {
+        assert_eq!(WatchdogAction::all().len(), 7);
+        assert_eq!(RebootAction::all().len(), 2);
+        assert_eq!(ShutdownAction::all().len(), 2);
+        assert_eq!(PanicAction::all().len(), 4);
+        assert!(WatchdogAction::all().contains(&WatchdogAction::InjectNmi));
+    }
+
+    #[test]
+    fn set_action_params_builder() {
+        let params = SetActionParams::builder()
+            .panic(PanicAction::ExitFailure)
+            .build();
+        assert_eq!(params.panic, Some(PanicAction::ExitFailure));
+        assert_eq!(params.reboot, None);
+        assert_eq!(params.shutdown, None);
+        assert_eq!(params.watchdog, None);
+    }
+
+    #[test]
+    fn set_action_params_effective() {
+        let params = SetActionParams::builder().panic(PanicAction::Pause).build();
+        assert_eq!(params.effective_panic(), Some(PanicAction::Pause));
+        assert_eq!(params.effective_reboot(), None);
+        assert_eq!(params.effective_shutdown(), None);
+        assert_eq!(params.effective_watchdog(), None);
+        assert_eq!(params.describe(), "panic: pause");
+
+        let params = SetActionParams::builder()
+            .panic(PanicAction::Shutdown)
+            .shutdown(ShutdownAction::Pause)
+            .build();
+        assert_eq!(
+            params.describe(),
+            "shutdown: pause, panic: shutdown (pause)"
+        );
+        assert_eq!(
+            SetActionParams::builder().build().describe(),
+            "no actions changed"
+        );
+    }
+
+    #[test]
+    fn watchdog_set_action_from_name() {
+        let cmd = watchdog_set_action_from_str("inject-nmi").unwrap();
+        let compact_string =
+            r#"{"execute":"watchdog-set-action","arguments":{"action":"inject-nmi"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        for action in WatchdogAction::all() {
+            assert_eq!(action.as_str().parse(), Ok(*action));
+        }
+        assert_eq!(
+            watchdog_set_action_from_str("explode"),
+            Err(WatchdogActionParseError {
+                input: "explode".to_string()
+            })
+        );
+    }
+
     // TODO new_watchdog_set_action
-    // TODO new_set_action
+
+    #[test]
+    fn command_names() {
+        assert_eq!(QueryVersion::NAME, query_version().execute);
+        assert_eq!(
+            WatchdogSetActionParams::NAME,
+            watchdog_set_action(WatchdogAction::Reset).execute
+        );
+        assert_eq!(
+            SetActionParams::NAME,
+            set_action(SetActionParams::default()).execute
+        );
+    }
+
+    #[test]
+    fn new_set_action() {
+        let params = SetActionParams::builder()
+            .reboot(RebootAction::Shutdown)
+            .shutdown(ShutdownAction::Pause)
+            .panic(PanicAction::None)
+            .watchdog(WatchdogAction::InjectNmi)
+            .build();
+        let cmd = set_action(params);
+        let compact_string = r#"{"execute":"set-action","arguments":{"reboot":"shutdown","shutdown":"pause","panic":"none","watchdog":"inject-nmi"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let cmd = set_action(SetActionParams::builder().panic(PanicAction::Pause).build());
+        let compact_string = r#"{"execute":"set-action","arguments":{"panic":"pause"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_commands_result() {
+        let cmd = query_commands();
+        let compact_string = r#"{"execute":"query-commands"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[{"name": "qom-list-types"}, {"name": "blockdev-add"},
+            {"name": "query-commands"}]"#;
+        let commands: <QueryCommands as QmpCommand>::Return = serde_json::from_str(string).unwrap();
+        let set = CommandInfoSet::from(commands);
+        assert!(set.contains("blockdev-add"));
+        assert!(set.contains("query-commands"));
+        assert!(!set.contains("x-unknown-command"));
+    }
 }
@@ -0,0 +1,235 @@
+//! QEMU Object Model (QOM).
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::generic::{Command, Empty, QmpCommand};
+
+/// Arguments of 'object-add'.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ObjectAddParams {
+    /// The class name for the object to be created.
+    pub qom_type: String,
+    /// The name of the new object.
+    pub id: String,
+    /// The properties of the object, flattened next to `qom-type` and `id`.
+    #[serde(flatten)]
+    pub props: Map<String, Value>,
+}
+
+impl QmpCommand for ObjectAddParams {
+    type Return = Empty;
+    const NAME: &'static str = "object-add";
+}
+
+/// Create a QOM object.
+///
+/// The available properties depend on `qom_type`; for memory backends,
+/// `object_add_memory_backend_ram` builds them from typed options.
+#[inline]
+pub fn object_add(
+    qom_type: String,
+    id: String,
+    props: Map<String, Value>,
+) -> Command<ObjectAddParams, ()> {
+    Command {
+        execute: "object-add".to_string(),
+        arguments: Some(ObjectAddParams {
+            qom_type,
+            id,
+            props,
+        }),
+        id: None,
+    }
+}
+
+/// Arguments of 'qom-set'.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QomSetParams {
+    /// The path within the object model.
+    pub path: String,
+    /// The property name to set.
+    pub property: String,
+    /// A value whose type is appropriate for the property type.
+    pub value: Value,
+}
+
+impl QmpCommand for QomSetParams {
+    type Return = Empty;
+    const NAME: &'static str = "qom-set";
+}
+
+/// Set a property value.
+///
+/// `qom_set_bool` and `qom_set_str` build the value for common scalar types.
+#[inline]
+pub fn qom_set(path: String, property: String, value: Value) -> Command<QomSetParams, ()> {
+    Command {
+        execute: "qom-set".to_string(),
+        arguments: Some(QomSetParams {
+            path,
+            property,
+            value,
+        }),
+        id: None,
+    }
+}
+
+/// Set a boolean property value.
+#[inline]
+pub fn qom_set_bool(path: String, property: String, value: bool) -> Command<QomSetParams, ()> {
+    qom_set(path, property, Value::Bool(value))
+}
+
+/// Set a string property value.
+#[inline]
+pub fn qom_set_str(path: String, property: String, value: String) -> Command<QomSetParams, ()> {
+    qom_set(path, property, Value::String(value))
+}
+
+/// Host memory policy types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HostMemPolicy {
+    /// Restore default policy, remove any nondefault policy.
+    Default,
+    /// Set the preferred host nodes for allocation.
+    Preferred,
+    /// A strict policy that restricts memory allocation to the host nodes specified.
+    Bind,
+    /// Memory allocations are interleaved across the set of host nodes specified.
+    Interleave,
+}
+
+impl HostMemPolicy {
+    /// Name of the policy, as used on the wire.
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            HostMemPolicy::Default => "default",
+            HostMemPolicy::Preferred => "preferred",
+            HostMemPolicy::Bind => "bind",
+            HostMemPolicy::Interleave => "interleave",
+        }
+    }
+}
+
+/// Optional properties of a 'memory-backend-ram' object.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MemoryBackendRamOpts {
+    /// If true, the memory is shared with other processes.
+    ///
+    /// Default is false.
+    pub share: Option<bool>,
+    /// If true, preallocate memory.
+    ///
+    /// Default is false.
+    pub prealloc: Option<bool>,
+    /// The list of NUMA host node ids the memory is bound to.
+    pub host_nodes: Option<Vec<u16>>,
+    /// The NUMA policy for the memory.
+    ///
+    /// Default is 'default'.
+    pub policy: Option<HostMemPolicy>,
+}
+
+/// Create a 'memory-backend-ram' object of `size` bytes.
+pub fn object_add_memory_backend_ram(
+    id: String,
+    size: u64,
+    opts: MemoryBackendRamOpts,
+) -> Command<ObjectAddParams, ()> {
+    let mut props = Map::new();
+    props.insert("size".to_string(), Value::from(size));
+    if let Some(share) = opts.share {
+        props.insert("share".to_string(), Value::from(share));
+    }
+    if let Some(prealloc) = opts.prealloc {
+        props.insert("prealloc".to_string(), Value::from(prealloc));
+    }
+    if let Some(host_nodes) = opts.host_nodes {
+        props.insert("host-nodes".to_string(), Value::from(host_nodes));
+    }
+    if let Some(policy) = opts.policy {
+        props.insert("policy".to_string(), Value::from(policy.as_str()));
+    }
+    object_add("memory-backend-ram".to_string(), id, props)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        HostMemPolicy, MemoryBackendRamOpts, object_add, object_add_memory_backend_ram, qom_set,
+        qom_set_bool, qom_set_str,
+    };
+    use alloc::{string::ToString, vec};
+
+    #[test]
+    fn new_object_add_memory_backend_ram() {
+        let cmd = object_add_memory_backend_ram(
+            "mem0".to_string(),
+            1073741824,
+            MemoryBackendRamOpts {
+                share: Some(true),
+                prealloc: None,
+                host_nodes: Some(vec![0, 1]),
+                policy: Some(HostMemPolicy::Bind),
+            },
+        );
+        let serde_json::Value::Object(props) = serde_json::json!({
+            "size": 1073741824u64,
+            "share": true,
+            "host-nodes": [0, 1],
+            "policy": "bind"
+        }) else {
+            unreachable!()
+        };
+        let raw = object_add("memory-backend-ram".to_string(), "mem0".to_string(), props);
+        assert_eq!(raw, cmd);
+        assert_eq!(
+            serde_json::to_value(&raw).unwrap(),
+            serde_json::to_value(&cmd).unwrap()
+        );
+        let string = serde_json::to_string(&cmd).unwrap();
+        assert!(string.contains(r#""qom-type":"memory-backend-ram","id":"mem0""#));
+    }
+
+    #[test]
+    fn new_qom_set() {
+        let cmd = qom_set(
+            "/machine".to_string(),
+            "graphics".to_string(),
+            serde_json::json!(false),
+        );
+        let compact_string = r#"{"execute":"qom-set","arguments":{"path":"/machine","property":"graphics","value":false}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn qom_set_scalar_wrappers() {
+        assert_eq!(
+            qom_set_bool("/machine".to_string(), "graphics".to_string(), true),
+            qom_set(
+                "/machine".to_string(),
+                "graphics".to_string(),
+                serde_json::json!(true)
+            )
+        );
+        assert_eq!(
+            qom_set_str(
+                "/objects/sec0".to_string(),
+                "file".to_string(),
+                "/run/secret".to_string()
+            ),
+            qom_set(
+                "/objects/sec0".to_string(),
+                "file".to_string(),
+                serde_json::json!("/run/secret")
+            )
+        );
+    }
+}
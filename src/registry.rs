@@ -0,0 +1,194 @@
+//! Construction of commands by name.
+#[cfg(feature = "json")]
+use alloc::string::ToString;
+#[cfg(feature = "json")]
+use serde_json::Value;
+
+use crate::acpi::QueryAcpiOspmStatus;
+#[cfg(feature = "json")]
+use crate::block::BlockdevReopenParams;
+use crate::block::{
+    BlockdevChangeMediumParams, BlockdevCloseTrayParams, BlockdevOpenTrayParams, BlockdevOptions,
+    EjectParams, QueryBlock, QueryBlockJobs, QueryBlockstatsParams, QueryNamedBlockNodesParams,
+    QueryPrManagers,
+};
+use crate::char_dev::ChardevAddParams;
+#[cfg(feature = "json")]
+use crate::cpu::{QueryCpuModelBaselineParams, QueryCpuModelComparisonParams};
+#[cfg(feature = "json")]
+use crate::generic::Command;
+use crate::generic::QmpCommand;
+use crate::job::QueryJobs;
+use crate::machine::{
+    CpuAddParams, QueryCurrentMachine, QueryMachines, QueryMemorySizeSummary, QueryVmGenerationId,
+    SystemPowerdown, SystemReset,
+};
+use crate::migration::{
+    CalcDirtyRateParams, CancelVcpuDirtyLimitParams, MigrateIncomingParams, MigrateParameters,
+    MigratePause, MigrateRecoverParams, MigrateSetCapabilitiesParams, MigrateStartPostcopy,
+    QueryColoStatus, QueryDirtyRate, QueryMigrate, QueryMigrateCapabilities,
+    QueryMigrateParameters, QueryVcpuDirtyLimit, QueryXenReplicationStatus,
+    SetVcpuDirtyLimitParams, SnapshotDeleteParams, SnapshotLoadParams, SnapshotSaveParams,
+    XColoLostHeartbeat, XenLoadDevicesStateParams, XenSaveDevicesStateParams,
+};
+use crate::misc::{
+    Cont, HumanMonitorCommandParams, QueryCommandLineOptionsParams, QueryFdsets, QueryIothreads,
+    QuerySgx, QuerySgxCapabilities, Quit, Stop,
+};
+use crate::qmp_monitor::{
+    QmpCapabilitiesParams, QueryCommands, QueryVersion, SetActionParams, WatchdogSetActionParams,
+};
+#[cfg(feature = "json")]
+use crate::qom::{ObjectAddParams, QomSetParams};
+use crate::replay::{QueryReplay, ReplayBreakParams, ReplaySeekParams};
+use crate::rocker::{QueryRockerOfDpaFlowsParams, QueryRockerParams, QueryRockerPortsParams};
+use crate::stats::{QueryStatsSchemasParams, StatsFilter};
+use crate::tpm::{QueryTpm, QueryTpmModels, QueryTpmTypes};
+use crate::trace::{TraceEventGetStateParams, TraceEventSetStateParams};
+use crate::ui::{DisplayReloadOptions, QuerySpice, QueryVnc};
+use crate::yank::{QueryYank, YankParams};
+
+/// Names of all the commands modeled by this library.
+///
+/// Commands whose arguments are raw json are only listed with the `json`
+/// feature.
+pub const COMMAND_NAMES: &[&str] = &[
+    QueryAcpiOspmStatus::NAME,
+    QueryNamedBlockNodesParams::NAME,
+    QueryBlock::NAME,
+    QueryBlockJobs::NAME,
+    QueryBlockstatsParams::NAME,
+    EjectParams::NAME,
+    BlockdevOpenTrayParams::NAME,
+    BlockdevCloseTrayParams::NAME,
+    BlockdevChangeMediumParams::NAME,
+    QueryPrManagers::NAME,
+    BlockdevOptions::NAME,
+    #[cfg(feature = "json")]
+    BlockdevReopenParams::NAME,
+    ChardevAddParams::NAME,
+    #[cfg(feature = "json")]
+    QueryCpuModelComparisonParams::NAME,
+    #[cfg(feature = "json")]
+    QueryCpuModelBaselineParams::NAME,
+    QueryJobs::NAME,
+    QueryCurrentMachine::NAME,
+    QueryMachines::NAME,
+    CpuAddParams::NAME,
+    QueryVmGenerationId::NAME,
+    QueryMemorySizeSummary::NAME,
+    SystemReset::NAME,
+    SystemPowerdown::NAME,
+    MigrateIncomingParams::NAME,
+    CalcDirtyRateParams::NAME,
+    QueryDirtyRate::NAME,
+    SetVcpuDirtyLimitParams::NAME,
+    CancelVcpuDirtyLimitParams::NAME,
+    QueryVcpuDirtyLimit::NAME,
+    MigrateStartPostcopy::NAME,
+    MigratePause::NAME,
+    MigrateRecoverParams::NAME,
+    MigrateSetCapabilitiesParams::NAME,
+    QueryMigrateCapabilities::NAME,
+    MigrateParameters::NAME,
+    QueryMigrateParameters::NAME,
+    QueryMigrate::NAME,
+    QueryXenReplicationStatus::NAME,
+    QueryColoStatus::NAME,
+    XColoLostHeartbeat::NAME,
+    SnapshotSaveParams::NAME,
+    SnapshotLoadParams::NAME,
+    SnapshotDeleteParams::NAME,
+    XenSaveDevicesStateParams::NAME,
+    XenLoadDevicesStateParams::NAME,
+    QueryIothreads::NAME,
+    QueryFdsets::NAME,
+    QuerySgx::NAME,
+    QuerySgxCapabilities::NAME,
+    Stop::NAME,
+    Cont::NAME,
+    Quit::NAME,
+    HumanMonitorCommandParams::NAME,
+    QueryCommandLineOptionsParams::NAME,
+    QmpCapabilitiesParams::NAME,
+    QueryVersion::NAME,
+    WatchdogSetActionParams::NAME,
+    SetActionParams::NAME,
+    QueryCommands::NAME,
+    #[cfg(feature = "json")]
+    ObjectAddParams::NAME,
+    #[cfg(feature = "json")]
+    QomSetParams::NAME,
+    QueryReplay::NAME,
+    ReplayBreakParams::NAME,
+    ReplaySeekParams::NAME,
+    QueryRockerParams::NAME,
+    QueryRockerPortsParams::NAME,
+    QueryRockerOfDpaFlowsParams::NAME,
+    StatsFilter::NAME,
+    QueryStatsSchemasParams::NAME,
+    QueryTpmModels::NAME,
+    QueryTpmTypes::NAME,
+    QueryTpm::NAME,
+    TraceEventGetStateParams::NAME,
+    TraceEventSetStateParams::NAME,
+    DisplayReloadOptions::NAME,
+    QueryVnc::NAME,
+    QuerySpice::NAME,
+    YankParams::NAME,
+    QueryYank::NAME,
+];
+
+/// Construct a command from its name and raw arguments.
+///
+/// Returns `None` if the command is not one of `COMMAND_NAMES`. A json-null
+/// `args` means that the command takes no arguments.
+#[cfg(feature = "json")]
+pub fn construct_command(name: &str, args: Value) -> Option<Command<Value, ()>> {
+    if !COMMAND_NAMES.contains(&name) {
+        return None;
+    }
+    let arguments = match args {
+        Value::Null => None,
+        args => Some(args),
+    };
+    Some(Command {
+        execute: name.to_string(),
+        arguments,
+        id: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::COMMAND_NAMES;
+    #[cfg(feature = "json")]
+    use {
+        super::construct_command,
+        serde_json::{Value, json},
+    };
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn construct_known_command() {
+        let cmd = construct_command("replay-break", json!({"icount": 220414})).unwrap();
+        let compact_string = r#"{"execute":"replay-break","arguments":{"icount":220414}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let cmd = construct_command("query-version", Value::Null).unwrap();
+        let compact_string = r#"{"execute":"query-version"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn construct_unknown_command() {
+        assert!(construct_command("x-no-such-command", Value::Null).is_none());
+    }
+
+    #[test]
+    fn command_names_unique() {
+        for (i, name) in COMMAND_NAMES.iter().enumerate() {
+            assert!(!COMMAND_NAMES[i + 1..].contains(name), "duplicate {name}");
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Record/replay.
+use alloc::string::{String, ToString};
+use serde::{Deserialize, Serialize};
+
+use crate::generic::{Command, Empty, QmpCommand};
+
+/// Mode of the replay subsystem.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReplayMode {
+    /// Normal execution mode. Replay or record are not enabled.
+    None,
+    /// Record mode. All non-deterministic data is written into the replay log.
+    Record,
+    /// Replay mode. Non-deterministic data required for system execution
+    /// is read from the log.
+    Play,
+}
+
+/// Record/replay information.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ReplayInfo {
+    /// Current mode.
+    pub mode: ReplayMode,
+    /// Name of the record/replay log file.
+    ///
+    /// It is present only in record or replay modes, when the log is recorded or replayed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    /// Current number of executed instructions.
+    pub icount: u64,
+}
+
+/// Marker of 'query-replay'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryReplay;
+
+impl QmpCommand for QueryReplay {
+    type Return = ReplayInfo;
+    const NAME: &'static str = "query-replay";
+}
+
+/// Retrieve the record/replay information.
+///
+/// It includes current instruction count which may be used for 'replay-break'
+/// and 'replay-seek' commands.
+#[inline]
+pub fn query_replay() -> Command<(), ()> {
+    Command {
+        execute: "query-replay".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Arguments of 'replay-break'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReplayBreakParams {
+    /// Instruction count to stop at.
+    pub icount: u64,
+}
+
+impl QmpCommand for ReplayBreakParams {
+    type Return = Empty;
+    const NAME: &'static str = "replay-break";
+}
+
+/// Set replay breakpoint at instruction count `icount`.
+///
+/// Execution stops when the specified instruction is reached.
+/// There can be at most one breakpoint. When breakpoint is set, any prior
+/// one is removed. The breakpoint may be set only in replay mode and only
+/// "in the future", i.e. at instruction counts greater than the current one.
+#[inline]
+pub fn replay_break(icount: u64) -> Command<ReplayBreakParams, ()> {
+    Command {
+        execute: "replay-break".to_string(),
+        arguments: Some(ReplayBreakParams { icount }),
+        id: None,
+    }
+}
+
+/// Arguments of 'replay-seek'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReplaySeekParams {
+    /// Target instruction count.
+    pub icount: u64,
+}
+
+impl QmpCommand for ReplaySeekParams {
+    type Return = Empty;
+    const NAME: &'static str = "replay-seek";
+}
+
+/// Automatically proceed to the instruction count `icount`, when replaying the execution.
+///
+/// The command automatically loads nearest snapshot and replays the execution
+/// to find the desired instruction. When there is no preceding snapshot or the
+/// execution is not replayed, then the command fails.
+#[inline]
+pub fn replay_seek(icount: u64) -> Command<ReplaySeekParams, ()> {
+    Command {
+        execute: "replay-seek".to_string(),
+        arguments: Some(ReplaySeekParams { icount }),
+        id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReplayInfo, ReplayMode, query_replay, replay_break, replay_seek};
+    use alloc::string::ToString;
+
+    #[test]
+    fn query_replay_result() {
+        let cmd = query_replay();
+        let compact_string = r#"{"execute":"query-replay"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"{"mode": "record", "filename": "log.rr", "icount": 220414}"#;
+        let value = ReplayInfo {
+            mode: ReplayMode::Record,
+            filename: Some("log.rr".to_string()),
+            icount: 220414,
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn new_replay_break() {
+        let cmd = replay_break(220414);
+        let compact_string = r#"{"execute":"replay-break","arguments":{"icount":220414}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_replay_seek() {
+        let cmd = replay_seek(220414);
+        let compact_string = r#"{"execute":"replay-seek","arguments":{"icount":220414}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+}
@@ -0,0 +1,290 @@
+//! Rocker switch device.
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::generic::{Command, QmpCommand};
+
+/// Rocker switch information.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct RockerSwitch {
+    /// Switch name.
+    pub name: String,
+    /// Switch ID.
+    pub id: u64,
+    /// Number of front-panel ports.
+    pub ports: u32,
+}
+
+/// Arguments of 'query-rocker'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct QueryRockerParams {
+    /// Switch name.
+    pub name: String,
+}
+
+impl QmpCommand for QueryRockerParams {
+    type Return = RockerSwitch;
+    const NAME: &'static str = "query-rocker";
+}
+
+/// Return rocker switch information.
+#[inline]
+pub fn query_rocker(name: String) -> Command<QueryRockerParams, ()> {
+    Command {
+        execute: "query-rocker".to_string(),
+        arguments: Some(QueryRockerParams { name }),
+        id: None,
+    }
+}
+
+/// Port duplex mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RockerPortDuplex {
+    /// Half duplex.
+    Half,
+    /// Full duplex.
+    Full,
+}
+
+/// Port auto-negotiation mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RockerPortAutoneg {
+    /// Auto-negotiation disabled.
+    Off,
+    /// Auto-negotiation enabled.
+    On,
+}
+
+/// Rocker switch port information.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct RockerPort {
+    /// Port name.
+    pub name: String,
+    /// Port is enabled for I/O.
+    pub enabled: bool,
+    /// Physical link is UP on port.
+    pub link_up: bool,
+    /// Port link speed in Mbps.
+    pub speed: u32,
+    /// Port link duplex.
+    pub duplex: RockerPortDuplex,
+    /// Port link auto-negotiation.
+    pub autoneg: RockerPortAutoneg,
+}
+
+/// Arguments of 'query-rocker-ports'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct QueryRockerPortsParams {
+    /// Port name.
+    pub name: String,
+}
+
+impl QmpCommand for QueryRockerPortsParams {
+    type Return = Vec<RockerPort>;
+    const NAME: &'static str = "query-rocker-ports";
+}
+
+/// Return rocker switch port information.
+#[inline]
+pub fn query_rocker_ports(name: String) -> Command<QueryRockerPortsParams, ()> {
+    Command {
+        execute: "query-rocker-ports".to_string(),
+        arguments: Some(QueryRockerPortsParams { name }),
+        id: None,
+    }
+}
+
+/// Rocker switch OF-DPA flow key.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct RockerOfDpaFlowKey {
+    /// Key priority, 0 being lowest priority.
+    pub priority: u32,
+    /// Flow table ID.
+    pub tbl_id: u32,
+    /// Physical input port.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_pport: Option<u32>,
+    /// Tunnel ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tunnel_id: Option<u32>,
+    /// VLAN ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vlan_id: Option<u16>,
+    /// Ethernet header type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_type: Option<u16>,
+    /// Ethernet header source MAC address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_src: Option<String>,
+    /// Ethernet header destination MAC address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_dst: Option<String>,
+    /// IP Header protocol field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_proto: Option<u8>,
+    /// IP header TOS field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_tos: Option<u8>,
+    /// IP header destination address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_dst: Option<String>,
+}
+
+/// Rocker switch OF-DPA flow mask.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct RockerOfDpaFlowMask {
+    /// Physical input port.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_pport: Option<u32>,
+    /// Tunnel ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tunnel_id: Option<u32>,
+    /// VLAN ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vlan_id: Option<u16>,
+    /// Ethernet header source MAC address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_src: Option<String>,
+    /// Ethernet header destination MAC address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_dst: Option<String>,
+    /// IP Header protocol field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_proto: Option<u8>,
+    /// IP header TOS field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_tos: Option<u8>,
+}
+
+/// Rocker switch OF-DPA flow action.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct RockerOfDpaFlowAction {
+    /// Next table ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goto_tbl: Option<u32>,
+    /// Group ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<u32>,
+    /// Tunnel logical port ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tunnel_lport: Option<u32>,
+    /// VLAN ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vlan_id: Option<u16>,
+    /// New VLAN ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_vlan_id: Option<u16>,
+    /// Physical output port.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_pport: Option<u32>,
+}
+
+/// Rocker switch OF-DPA flow.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct RockerOfDpaFlow {
+    /// Flow unique cookie ID.
+    pub cookie: u64,
+    /// Count of matches (hits) on flow.
+    pub hits: u64,
+    /// Flow key.
+    pub key: RockerOfDpaFlowKey,
+    /// Flow mask.
+    pub mask: RockerOfDpaFlowMask,
+    /// Flow action.
+    pub action: RockerOfDpaFlowAction,
+}
+
+/// Arguments of 'query-rocker-of-dpa-flows'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct QueryRockerOfDpaFlowsParams {
+    /// Switch name.
+    pub name: String,
+    /// Flow table ID. If tbl-id is not specified, returns flow information
+    /// for all tables.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tbl_id: Option<u32>,
+}
+
+impl QmpCommand for QueryRockerOfDpaFlowsParams {
+    type Return = Vec<RockerOfDpaFlow>;
+    const NAME: &'static str = "query-rocker-of-dpa-flows";
+}
+
+/// Return rocker OF-DPA flow information.
+#[inline]
+pub fn query_rocker_of_dpa_flows(
+    name: String,
+    tbl_id: Option<u32>,
+) -> Command<QueryRockerOfDpaFlowsParams, ()> {
+    Command {
+        execute: "query-rocker-of-dpa-flows".to_string(),
+        arguments: Some(QueryRockerOfDpaFlowsParams { name, tbl_id }),
+        id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        RockerOfDpaFlow, RockerSwitch, query_rocker, query_rocker_of_dpa_flows, query_rocker_ports,
+    };
+    use alloc::{string::ToString, vec::Vec};
+
+    #[test]
+    fn new_query_rocker() {
+        let cmd = query_rocker("sw1".to_string());
+        let compact_string = r#"{"execute":"query-rocker","arguments":{"name":"sw1"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_rocker_result() {
+        let string = r#"{"name": "rocker1", "id": 1, "ports": 2}"#;
+        let value = RockerSwitch {
+            name: "rocker1".to_string(),
+            id: 1,
+            ports: 2,
+        };
+        assert_eq!(value, serde_json::from_str(string).unwrap());
+    }
+
+    #[test]
+    fn new_query_rocker_ports() {
+        let cmd = query_rocker_ports("sw1".to_string());
+        let compact_string = r#"{"execute":"query-rocker-ports","arguments":{"name":"sw1"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_query_rocker_of_dpa_flows() {
+        let cmd = query_rocker_of_dpa_flows("sw1".to_string(), Some(10));
+        let compact_string =
+            r#"{"execute":"query-rocker-of-dpa-flows","arguments":{"name":"sw1","tbl-id":10}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_rocker_of_dpa_flows_result() {
+        let string = r#"[{"key": {"in-pport": 0, "priority": 1, "tbl-id": 0},
+            "hits": 138, "cookie": 0, "action": {"goto-tbl": 10}, "mask": {"in-pport": 4294901760}}]"#;
+        let flows: Vec<RockerOfDpaFlow> = serde_json::from_str(string).unwrap();
+        assert_eq!(flows[0].hits, 138);
+        assert_eq!(flows[0].key.in_pport, Some(0));
+        assert_eq!(flows[0].action.goto_tbl, Some(10));
+        assert_eq!(flows[0].mask.in_pport, Some(4294901760));
+    }
+}
@@ -0,0 +1,329 @@
+//! Socket data types.
+use alloc::string::{String, ToString};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// The network address family.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NetworkAddressFamily {
+    /// IPV4 family.
+    Ipv4,
+    /// IPV6 family.
+    Ipv6,
+    /// Unix domain socket.
+    Unix,
+    /// Vsock family.
+    Vsock,
+    /// Otherwise.
+    Unknown,
+}
+
+/// Captures a socket address or address range in the Internet namespace.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct InetSocketAddress {
+    /// Host part of the address.
+    pub host: String,
+    /// Port part of the address.
+    pub port: String,
+    /// Whether to accept the resolved addresses as numeric strings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub numeric: Option<bool>,
+    /// If present, this is range of possible addresses, with port between `port` and `to`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<u16>,
+    /// Whether to accept IPv4 addresses, default try both IPv4 and IPv6.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipv4: Option<bool>,
+    /// Whether to accept IPv6 addresses, default try both IPv4 and IPv6.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipv6: Option<bool>,
+}
+
+/// A local address.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UnixSocketAddress {
+    /// Filesystem path to use.
+    pub path: String,
+}
+
+/// A vsock (virtio-vsock) address.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct VsockSocketAddress {
+    /// Context identifier.
+    pub cid: String,
+    /// Port.
+    pub port: String,
+}
+
+/// A file descriptor name or number.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FdSocketAddress {
+    /// Decimal is for file descriptor number, otherwise it's a file
+    /// descriptor name. Named file descriptors are permitted in
+    /// monitor commands, in combination with the 'getfd' command.
+    /// Decimal file descriptors are permitted at startup or other
+    /// contexts where no monitor context is active.
+    pub str: String,
+}
+
+/// Captures the address of a socket, which could also be a socket file descriptor.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum SocketAddress {
+    /// Internet address.
+    Inet(InetSocketAddress),
+    /// Unix domain socket.
+    Unix(UnixSocketAddress),
+    /// VMCI address.
+    Vsock(VsockSocketAddress),
+    /// Socket file descriptor.
+    Fd(FdSocketAddress),
+}
+
+/// Error of parsing a `SocketAddress` from a uri.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SocketAddressParseError {
+    /// The uri has no `scheme:` prefix.
+    MissingScheme,
+    /// The scheme is not one of 'tcp', 'unix' or 'vsock'.
+    UnknownScheme(String),
+    /// A 'tcp' or 'vsock' uri has no port.
+    MissingPort,
+    /// The bracketed host, path, cid or port is empty.
+    EmptyComponent,
+    /// The port is not a number in range.
+    InvalidPort(String),
+    /// A bracketed IPv6 host is not closed, or a bracket appears outside one.
+    UnbalancedBracket,
+}
+
+impl fmt::Display for SocketAddressParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SocketAddressParseError::MissingScheme => f.write_str("missing uri scheme"),
+            SocketAddressParseError::UnknownScheme(scheme) => {
+                write!(f, "unknown uri scheme `{scheme}`")
+            }
+            SocketAddressParseError::MissingPort => f.write_str("missing port"),
+            SocketAddressParseError::EmptyComponent => f.write_str("empty address component"),
+            SocketAddressParseError::InvalidPort(port) => write!(f, "invalid port `{port}`"),
+            SocketAddressParseError::UnbalancedBracket => f.write_str("unbalanced bracket in host"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SocketAddressParseError {}
+
+impl SocketAddress {
+    /// Parse a socket address in the uri form of 'migrate' and friends.
+    ///
+    /// Accepts `tcp:host:port`, `unix:/path` and `vsock:cid:port`. IPv6
+    /// hosts are written in brackets, as in `tcp:[::1]:4446`; an empty host,
+    /// as in `tcp::4446`, listens on all addresses.
+    pub fn from_uri(s: &str) -> Result<SocketAddress, SocketAddressParseError> {
+        let (scheme, rest) = s
+            .split_once(':')
+            .ok_or(SocketAddressParseError::MissingScheme)?;
+        match scheme {
+            "tcp" => {
+                let (host, port) = match rest.strip_prefix('[') {
+                    Some(rest) => {
+                        let (host, rest) = rest
+                            .split_once(']')
+                            .ok_or(SocketAddressParseError::UnbalancedBracket)?;
+                        let port = rest
+                            .strip_prefix(':')
+                            .ok_or(SocketAddressParseError::MissingPort)?;
+                        if host.is_empty() {
+                            return Err(SocketAddressParseError::EmptyComponent);
+                        }
+                        (host, check_port(port)?)
+                    }
+                    None => split_port(rest)?,
+                };
+                if host.contains(['[', ']']) {
+                    return Err(SocketAddressParseError::UnbalancedBracket);
+                }
+                if port.parse::<u16>().is_err() {
+                    return Err(SocketAddressParseError::InvalidPort(port.to_string()));
+                }
+                Ok(SocketAddress::Inet(InetSocketAddress {
+                    host: host.to_string(),
+                    port: port.to_string(),
+                    numeric: None,
+                    to: None,
+                    ipv4: None,
+                    ipv6: None,
+                }))
+            }
+            "unix" => {
+                if rest.is_empty() {
+                    return Err(SocketAddressParseError::EmptyComponent);
+                }
+                Ok(SocketAddress::Unix(UnixSocketAddress {
+                    path: rest.to_string(),
+                }))
+            }
+            "vsock" => {
+                let (cid, port) = split_port(rest)?;
+                if port.parse::<u32>().is_err() {
+                    return Err(SocketAddressParseError::InvalidPort(port.to_string()));
+                }
+                if cid.is_empty() {
+                    return Err(SocketAddressParseError::EmptyComponent);
+                }
+                Ok(SocketAddress::Vsock(VsockSocketAddress {
+                    cid: cid.to_string(),
+                    port: port.to_string(),
+                }))
+            }
+            scheme => Err(SocketAddressParseError::UnknownScheme(scheme.to_string())),
+        }
+    }
+}
+
+// Split the last `:port` off an address, the host may contain colons.
+fn split_port(s: &str) -> Result<(&str, &str), SocketAddressParseError> {
+    let (host, port) = s
+        .rsplit_once(':')
+        .ok_or(SocketAddressParseError::MissingPort)?;
+    Ok((host, check_port(port)?))
+}
+
+// Ports are given as decimal numbers; their range is checked by the caller.
+fn check_port(port: &str) -> Result<&str, SocketAddressParseError> {
+    if port.is_empty() {
+        return Err(SocketAddressParseError::EmptyComponent);
+    }
+    if !port.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(SocketAddressParseError::InvalidPort(port.to_string()));
+    }
+    Ok(port)
+}
+
+/// Captures the address of a socket, in the legacy form nesting the address
+/// under 'data'.
+///
+/// This form is used by 'chardev-add' and a few older commands.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "kebab-case")]
+pub enum SocketAddressLegacy {
+    /// Internet address.
+    Inet(InetSocketAddress),
+    /// Unix domain socket.
+    Unix(UnixSocketAddress),
+    /// VMCI address.
+    Vsock(VsockSocketAddress),
+    /// Socket file descriptor.
+    Fd(FdSocketAddress),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        InetSocketAddress, SocketAddress, SocketAddressParseError, UnixSocketAddress,
+        VsockSocketAddress,
+    };
+    use alloc::string::ToString;
+
+    #[test]
+    fn socket_address_from_uri() {
+        let value = SocketAddress::Inet(InetSocketAddress {
+            host: "192.168.1.200".to_string(),
+            port: "4446".to_string(),
+            numeric: None,
+            to: None,
+            ipv4: None,
+            ipv6: None,
+        });
+        assert_eq!(Ok(value), SocketAddress::from_uri("tcp:192.168.1.200:4446"));
+        match SocketAddress::from_uri("tcp:[::1]:4446").unwrap() {
+            SocketAddress::Inet(inet) => assert_eq!(inet.host, "::1"),
+            other => panic!("unexpected address: {other:?}"),
+        }
+        match SocketAddress::from_uri("tcp::4446").unwrap() {
+            SocketAddress::Inet(inet) => {
+                assert_eq!(inet.host, "");
+                assert_eq!(inet.port, "4446");
+            }
+            other => panic!("unexpected address: {other:?}"),
+        }
+        let value = SocketAddress::Unix(UnixSocketAddress {
+            path: "/tmp/migrate.sock".to_string(),
+        });
+        assert_eq!(Ok(value), SocketAddress::from_uri("unix:/tmp/migrate.sock"));
+        let value = SocketAddress::Vsock(VsockSocketAddress {
+            cid: "3".to_string(),
+            port: "1234".to_string(),
+        });
+        assert_eq!(Ok(value), SocketAddress::from_uri("vsock:3:1234"));
+        let compact_string = r#"{"type":"vsock","cid":"3","port":"1234"}"#;
+        let address = SocketAddress::from_uri("vsock:3:1234").unwrap();
+        assert_eq!(compact_string, serde_json::to_string(&address).unwrap());
+    }
+
+    #[test]
+    fn socket_address_from_malformed_uri() {
+        let parse = SocketAddress::from_uri;
+        assert_eq!(
+            parse("localhost"),
+            Err(SocketAddressParseError::MissingScheme)
+        );
+        assert_eq!(
+            parse("rdma:host:4446"),
+            Err(SocketAddressParseError::UnknownScheme("rdma".to_string()))
+        );
+        assert_eq!(
+            parse("tcp:localhost"),
+            Err(SocketAddressParseError::MissingPort)
+        );
+        assert_eq!(
+            parse("vsock:3:"),
+            Err(SocketAddressParseError::EmptyComponent)
+        );
+        assert_eq!(parse("unix:"), Err(SocketAddressParseError::EmptyComponent));
+        assert_eq!(
+            parse("tcp:localhost:http"),
+            Err(SocketAddressParseError::InvalidPort("http".to_string()))
+        );
+        assert_eq!(
+            parse("tcp:localhost:65536"),
+            Err(SocketAddressParseError::InvalidPort("65536".to_string()))
+        );
+        assert_eq!(
+            parse("vsock:3:-1"),
+            Err(SocketAddressParseError::InvalidPort("-1".to_string()))
+        );
+    }
+
+    #[test]
+    fn socket_address_from_bracketed_uri() {
+        let parse = SocketAddress::from_uri;
+        match parse("tcp:[fe80::1%eth0]:4446").unwrap() {
+            SocketAddress::Inet(inet) => {
+                assert_eq!(inet.host, "fe80::1%eth0");
+                assert_eq!(inet.port, "4446");
+            }
+            other => panic!("unexpected address: {other:?}"),
+        }
+        assert_eq!(
+            parse("tcp:[::1]"),
+            Err(SocketAddressParseError::MissingPort)
+        );
+        assert_eq!(
+            parse("tcp:[::1:4446"),
+            Err(SocketAddressParseError::UnbalancedBracket)
+        );
+        assert_eq!(
+            parse("tcp:::1]:4446"),
+            Err(SocketAddressParseError::UnbalancedBracket)
+        );
+        assert_eq!(
+            parse("tcp:[]:4446"),
+            Err(SocketAddressParseError::EmptyComponent)
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Statistics.
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::generic::{Command, QmpCommand};
+
+/// Enumeration of statistics types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatsType {
+    /// Stat is cumulative; value can only increase.
+    Cumulative,
+    /// Stat is instantaneous; value can increase or decrease.
+    Instant,
+    /// Stat is the peak value; value can only increase.
+    Peak,
+    /// Stat is a linear histogram.
+    LinearHistogram,
+    /// Stat is a logarithmic histogram, with one bucket for each power of two.
+    Log2Histogram,
+}
+
+/// Enumeration of unit of measurement for statistics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatsUnit {
+    /// Stat reported in bytes.
+    Bytes,
+    /// Stat reported in seconds.
+    Seconds,
+    /// Stat reported in clock cycles.
+    Cycles,
+    /// Stat is a boolean value.
+    Boolean,
+}
+
+/// Enumeration of statistics providers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatsProvider {
+    /// Statistics that are available from KVM.
+    Kvm,
+    /// Statistics that are available from cryptodev.
+    Cryptodev,
+}
+
+/// The kinds of objects on which one can request statistics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatsTarget {
+    /// Statistics that apply to the entire virtual machine or the entire QEMU process.
+    Vm,
+    /// Statistics that apply to a single virtual CPU.
+    Vcpu,
+    /// Statistics for cryptodev backends.
+    Cryptodev,
+}
+
+/// Indicates a set of statistics that should be returned by 'query-stats'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StatsRequest {
+    /// Provider for which to return statistics.
+    pub provider: StatsProvider,
+    /// Statistics to be returned.
+    ///
+    /// If not present, all statistics for the provider are returned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub names: Option<Vec<String>>,
+}
+
+/// Arguments of 'query-stats'.
+///
+/// The arguments are a union on `target`; `vcpus` only applies to the
+/// 'vcpu' target.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StatsFilter {
+    /// The kind of objects to query.
+    pub target: StatsTarget,
+    /// Which providers to request statistics from, and optionally which
+    /// named values to return within each provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub providers: Option<Vec<StatsRequest>>,
+    /// List of QOM paths for the desired vCPU objects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcpus: Option<Vec<String>>,
+}
+
+/// The value of a statistic.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StatsValue {
+    /// Single unsigned 64-bit integers.
+    Scalar(u64),
+    /// Single boolean value.
+    Boolean(bool),
+    /// List of unsigned 64-bit integers, used for histograms.
+    List(Vec<u64>),
+}
+
+/// A statistic by name and value.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Stat {
+    /// Name of the statistic.
+    pub name: String,
+    /// Value of the statistic.
+    pub value: StatsValue,
+}
+
+/// Statistics of one provider for one object.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct StatsResult {
+    /// Provider for this set of statistics.
+    pub provider: StatsProvider,
+    /// Path to the object for which the statistics are returned, if the
+    /// object is exposed in the QOM tree.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qom_path: Option<String>,
+    /// List of statistics returned by the provider.
+    pub stats: Vec<Stat>,
+}
+
+impl QmpCommand for StatsFilter {
+    type Return = Vec<StatsResult>;
+    const NAME: &'static str = "query-stats";
+}
+
+/// Return runtime-collected statistics for objects such as the VM or its vCPUs.
+///
+/// The arguments are a `StatsFilter` and specify the provider and objects
+/// to return statistics about.
+#[inline]
+pub fn query_stats(params: StatsFilter) -> Command<StatsFilter, ()> {
+    Command {
+        execute: "query-stats".to_string(),
+        arguments: Some(params),
+        id: None,
+    }
+}
+
+/// Schema for a single statistic.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct StatsSchemaValue {
+    /// Name of the statistic; each element of the schema is uniquely
+    /// identified by a target, a provider and a name.
+    pub name: String,
+    /// Kind of statistic.
+    #[serde(rename = "type")]
+    pub stats_type: StatsType,
+    /// Basic unit of measure for the statistic.
+    ///
+    /// If missing, the statistic is a simple number or counter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<StatsUnit>,
+    /// Base for the multiple of the unit in which the statistic is measured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<i8>,
+    /// Exponent for the multiple of the unit in which the statistic is
+    /// expressed, or 0 for the basic unit.
+    pub exponent: i16,
+    /// Present when `type` is "linear-histogram", contains the width of
+    /// each bucket of the histogram.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bucket_size: Option<u32>,
+}
+
+/// Schema for all available statistics for a provider and target.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct StatsSchema {
+    /// Provider for a set of statistics.
+    pub provider: StatsProvider,
+    /// The kind of object that can be queried through the provider.
+    pub target: StatsTarget,
+    /// List of statistics whose values can be obtained.
+    pub stats: Vec<StatsSchemaValue>,
+}
+
+/// Arguments of 'query-stats-schemas'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct QueryStatsSchemasParams {
+    /// If given, only the schemas of this provider are returned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<StatsProvider>,
+}
+
+impl QmpCommand for QueryStatsSchemasParams {
+    type Return = Vec<StatsSchema>;
+    const NAME: &'static str = "query-stats-schemas";
+}
+
+/// Return the schema for all available runtime-collected statistics.
+#[inline]
+pub fn query_stats_schemas(
+    provider: Option<StatsProvider>,
+) -> Command<QueryStatsSchemasParams, ()> {
+    Command {
+        execute: "query-stats-schemas".to_string(),
+        arguments: Some(QueryStatsSchemasParams { provider }),
+        id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Stat, StatsFilter, StatsProvider, StatsRequest, StatsResult, StatsTarget, StatsValue,
+        query_stats, query_stats_schemas,
+    };
+    use alloc::{string::ToString, vec, vec::Vec};
+
+    #[test]
+    fn new_query_stats() {
+        let cmd = query_stats(StatsFilter {
+            target: StatsTarget::Vcpu,
+            providers: Some(vec![StatsRequest {
+                provider: StatsProvider::Kvm,
+                names: Some(vec!["exits".to_string()]),
+            }]),
+            vcpus: Some(vec!["/machine/unattached/device[0]".to_string()]),
+        });
+        let compact_string = r#"{"execute":"query-stats","arguments":{"target":"vcpu","providers":[{"provider":"kvm","names":["exits"]}],"vcpus":["/machine/unattached/device[0]"]}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn new_query_stats_schemas() {
+        let cmd = query_stats_schemas(Some(StatsProvider::Kvm));
+        let compact_string = r#"{"execute":"query-stats-schemas","arguments":{"provider":"kvm"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_stats_result() {
+        let string = r#"[{"provider": "kvm", "qom-path": "/machine/unattached/device[0]",
+            "stats": [{"name": "guest_mode", "value": false},
+                      {"name": "exits", "value": 95176},
+                      {"name": "halt_poll_fail_hist", "value": [0, 2, 5]}]}]"#;
+        let value = vec![StatsResult {
+            provider: StatsProvider::Kvm,
+            qom_path: Some("/machine/unattached/device[0]".to_string()),
+            stats: vec![
+                Stat {
+                    name: "guest_mode".to_string(),
+                    value: StatsValue::Boolean(false),
+                },
+                Stat {
+                    name: "exits".to_string(),
+                    value: StatsValue::Scalar(95176),
+                },
+                Stat {
+                    name: "halt_poll_fail_hist".to_string(),
+                    value: StatsValue::List(vec![0, 2, 5]),
+                },
+            ],
+        }];
+        assert_eq!(value, serde_json::from_str::<Vec<_>>(string).unwrap());
+    }
+}
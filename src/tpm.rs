@@ -0,0 +1,194 @@
+//! Trusted Platform Module (TPM) devices.
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::generic::{Command, QmpCommand};
+
+/// An enumeration of TPM models.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TpmModel {
+    /// TPM TIS model.
+    TpmTis,
+    /// TPM CRB model.
+    TpmCrb,
+    /// TPM SPAPR model.
+    TpmSpapr,
+}
+
+/// Marker of 'query-tpm-models'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryTpmModels;
+
+impl QmpCommand for QueryTpmModels {
+    type Return = Vec<TpmModel>;
+    const NAME: &'static str = "query-tpm-models";
+}
+
+/// Return a list of supported TPM models.
+#[inline]
+pub fn query_tpm_models() -> Command<(), ()> {
+    Command {
+        execute: "query-tpm-models".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// An enumeration of TPM types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TpmType {
+    /// TPM passthrough type.
+    Passthrough,
+    /// Software Emulator TPM type.
+    Emulator,
+}
+
+/// Marker of 'query-tpm-types'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryTpmTypes;
+
+impl QmpCommand for QueryTpmTypes {
+    type Return = Vec<TpmType>;
+    const NAME: &'static str = "query-tpm-types";
+}
+
+/// Return a list of supported TPM types.
+#[inline]
+pub fn query_tpm_types() -> Command<(), ()> {
+    Command {
+        execute: "query-tpm-types".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// Information about the TPM passthrough type.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct TpmPassthroughOptions {
+    /// String describing the path used for accessing the TPM device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// String showing the TPM's sysfs cancel file for cancellation of
+    /// TPM commands while they are executing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cancel_path: Option<String>,
+}
+
+/// Information about the TPM emulator type.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct TpmEmulatorOptions {
+    /// Name of a unix socket chardev.
+    pub chardev: String,
+}
+
+/// A union referencing different TPM backend types' configuration options.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "kebab-case")]
+pub enum TpmTypeOptions {
+    /// The configuration options for the TPM passthrough type.
+    Passthrough(TpmPassthroughOptions),
+    /// The configuration options for TPM emulator backend type.
+    Emulator(TpmEmulatorOptions),
+}
+
+/// Information about the TPM.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct TpmInfo {
+    /// The Id of the TPM.
+    pub id: String,
+    /// The TPM frontend model.
+    pub model: TpmModel,
+    /// The TPM (backend) type configuration options.
+    pub options: TpmTypeOptions,
+}
+
+impl TpmInfo {
+    /// The host device path of a passthrough TPM.
+    ///
+    /// Returns `None` for other backend types or when no path is reported.
+    pub fn device_path(&self) -> Option<&str> {
+        match &self.options {
+            TpmTypeOptions::Passthrough(options) => options.path.as_deref(),
+            TpmTypeOptions::Emulator(_) => None,
+        }
+    }
+}
+
+/// Marker of 'query-tpm'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryTpm;
+
+impl QmpCommand for QueryTpm {
+    type Return = Vec<TpmInfo>;
+    const NAME: &'static str = "query-tpm";
+}
+
+/// Return information about the TPM device.
+#[inline]
+pub fn query_tpm() -> Command<(), ()> {
+    Command {
+        execute: "query-tpm".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        TpmEmulatorOptions, TpmInfo, TpmModel, TpmPassthroughOptions, TpmType, TpmTypeOptions,
+        query_tpm, query_tpm_types,
+    };
+    use alloc::{string::ToString, vec, vec::Vec};
+
+    #[test]
+    fn query_tpm_types_result() {
+        let cmd = query_tpm_types();
+        let compact_string = r#"{"execute":"query-tpm-types"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let types: Vec<TpmType> = serde_json::from_str(r#"["passthrough", "emulator"]"#).unwrap();
+        assert_eq!(types, vec![TpmType::Passthrough, TpmType::Emulator]);
+    }
+
+    #[test]
+    fn query_tpm_passthrough() {
+        let cmd = query_tpm();
+        let compact_string = r#"{"execute":"query-tpm"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[{"model": "tpm-tis", "options": {"type": "passthrough",
+            "data": {"cancel-path": "/sys/class/misc/tpm0/device/cancel",
+            "path": "/dev/tpm0"}}, "id": "tpm0"}]"#;
+        let value = vec![TpmInfo {
+            id: "tpm0".to_string(),
+            model: TpmModel::TpmTis,
+            options: TpmTypeOptions::Passthrough(TpmPassthroughOptions {
+                path: Some("/dev/tpm0".to_string()),
+                cancel_path: Some("/sys/class/misc/tpm0/device/cancel".to_string()),
+            }),
+        }];
+        let tpms: Vec<TpmInfo> = serde_json::from_str(string).unwrap();
+        assert_eq!(value, tpms);
+        assert_eq!(tpms[0].device_path(), Some("/dev/tpm0"));
+    }
+
+    #[test]
+    fn query_tpm_emulator() {
+        let string = r#"{"model": "tpm-crb", "options": {"type": "emulator",
+            "data": {"chardev": "chrtpm"}}, "id": "tpm1"}"#;
+        let tpm: TpmInfo = serde_json::from_str(string).unwrap();
+        assert_eq!(
+            tpm.options,
+            TpmTypeOptions::Emulator(TpmEmulatorOptions {
+                chardev: "chrtpm".to_string()
+            })
+        );
+        assert_eq!(tpm.device_path(), None);
+    }
+}
@@ -0,0 +1,151 @@
+//! Tracing.
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::generic::{Command, Empty, QmpCommand};
+
+/// State of a tracing event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TraceEventState {
+    /// The event is statically disabled.
+    Unavailable,
+    /// The event is dynamically disabled.
+    Disabled,
+    /// The event is dynamically enabled.
+    Enabled,
+}
+
+/// Information of a tracing event.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct TraceEventInfo {
+    /// Event name.
+    pub name: String,
+    /// Tracing state.
+    pub state: TraceEventState,
+    /// Whether this is a per-vCPU event.
+    ///
+    /// Newer QEMU releases no longer report this member.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcpu: Option<bool>,
+}
+
+/// Arguments of 'trace-event-get-state'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TraceEventGetStateParams {
+    /// Event name pattern (case-sensitive glob).
+    pub name: String,
+    /// The vCPU to query (deprecated, ignored).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcpu: Option<i64>,
+}
+
+impl QmpCommand for TraceEventGetStateParams {
+    type Return = Vec<TraceEventInfo>;
+    const NAME: &'static str = "trace-event-get-state";
+}
+
+/// Query the state of events.
+///
+/// The server returns a list of `TraceEventInfo` for the matching events.
+#[inline]
+pub fn trace_event_get_state(
+    name: String,
+    vcpu: Option<i64>,
+) -> Command<TraceEventGetStateParams, ()> {
+    Command {
+        execute: "trace-event-get-state".to_string(),
+        arguments: Some(TraceEventGetStateParams { name, vcpu }),
+        id: None,
+    }
+}
+
+/// Arguments of 'trace-event-set-state'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TraceEventSetStateParams {
+    /// Event name pattern (case-sensitive glob).
+    pub name: String,
+    /// Whether to enable tracing.
+    pub enable: bool,
+    /// Do not match unavailable events with `name`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_unavailable: Option<bool>,
+    /// The vCPU to act upon (deprecated, ignored).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcpu: Option<i64>,
+}
+
+impl QmpCommand for TraceEventSetStateParams {
+    type Return = Empty;
+    const NAME: &'static str = "trace-event-set-state";
+}
+
+/// Set the dynamic tracing state of events.
+#[inline]
+pub fn trace_event_set_state(
+    name: String,
+    enable: bool,
+    ignore_unavailable: Option<bool>,
+    vcpu: Option<i64>,
+) -> Command<TraceEventSetStateParams, ()> {
+    Command {
+        execute: "trace-event-set-state".to_string(),
+        arguments: Some(TraceEventSetStateParams {
+            name,
+            enable,
+            ignore_unavailable,
+            vcpu,
+        }),
+        id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TraceEventInfo, TraceEventState, trace_event_get_state, trace_event_set_state};
+    use alloc::{string::ToString, vec, vec::Vec};
+
+    #[test]
+    fn trace_event_get_state_result() {
+        let cmd = trace_event_get_state("qemu_memalign".to_string(), None);
+        let compact_string =
+            r#"{"execute":"trace-event-get-state","arguments":{"name":"qemu_memalign"}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let cmd = trace_event_get_state("qemu_*".to_string(), Some(0));
+        let compact_string =
+            r#"{"execute":"trace-event-get-state","arguments":{"name":"qemu_*","vcpu":0}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[
+            {"name": "qemu_memalign", "state": "disabled", "vcpu": false},
+            {"name": "qemu_vfree", "state": "enabled"}
+        ]"#;
+        let value = vec![
+            TraceEventInfo {
+                name: "qemu_memalign".to_string(),
+                state: TraceEventState::Disabled,
+                vcpu: Some(false),
+            },
+            TraceEventInfo {
+                name: "qemu_vfree".to_string(),
+                state: TraceEventState::Enabled,
+                vcpu: None,
+            },
+        ];
+        assert_eq!(value, serde_json::from_str::<Vec<_>>(string).unwrap());
+    }
+
+    #[test]
+    fn new_trace_event_set_state() {
+        let cmd = trace_event_set_state("qemu_memalign".to_string(), true, None, None);
+        let compact_string = r#"{"execute":"trace-event-set-state","arguments":{"name":"qemu_memalign","enable":true}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let cmd = trace_event_set_state("qemu_*".to_string(), false, Some(true), None);
+        let compact_string = r#"{"execute":"trace-event-set-state","arguments":{"name":"qemu_*","enable":false,"ignore-unavailable":true}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+}
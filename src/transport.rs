@@ -0,0 +1,1003 @@
+//! Client-side message transport.
+use crate::events::AnyEvent;
+use crate::generic::{
+    Command, Event, IdGenerator, QmpError, Response, ReturnOrError, ServerGreeting, SimpleCommand,
+    Timestamp,
+};
+use crate::machine::{system_powerdown, system_reset};
+use crate::misc::{cont, stop};
+use crate::qmp_monitor::{VersionInfo, qmp_capabilities, query_version};
+use crate::vm_run_state::{ResetEvent, ShutdownEvent};
+use alloc::{string::String, vec::Vec};
+use core::{fmt, mem};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::time::{Duration, Instant};
+
+/// A message issued by the Server after capabilities negotiation.
+///
+/// Once in command mode, the Server either answers a command with
+/// a response, or sends an asynchronous event at any time.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ServerMessage<T, U, E> {
+    /// A response to a command issued by the Client.
+    Response(Response<T, U>),
+    /// An asynchronous event.
+    Event(Event<E>),
+}
+
+/// Result of correlating a server message against pending command ids.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Correlation<T, U, E> {
+    /// The response carries an id that was registered as pending.
+    Matched(Response<T, U>),
+    /// The response carries no id, or an id that is not pending.
+    ///
+    /// Clients should drop all the responses that have an unknown id field.
+    Unmatched(Response<T, U>),
+    /// The message is an asynchronous event, which never carries an id.
+    Event(Event<E>),
+}
+
+/// Matches responses to the ids of pipelined commands.
+///
+/// When several commands are issued with ids before their responses are read,
+/// the responses may arrive in any order and interleaved with events.
+/// Register each id when its command is sent, then feed every incoming server
+/// message through [`IdCorrelator::match_response`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IdCorrelator<U> {
+    pending: Vec<U>,
+}
+
+impl<U: PartialEq> IdCorrelator<U> {
+    /// Create a correlator with no pending ids.
+    #[inline]
+    pub const fn new() -> Self {
+        IdCorrelator {
+            pending: Vec::new(),
+        }
+    }
+
+    /// Register the id of a command that was sent and awaits its response.
+    #[inline]
+    pub fn register(&mut self, id: U) {
+        self.pending.push(id);
+    }
+
+    /// Returns whether a response with the given id is still awaited.
+    #[inline]
+    pub fn is_pending(&self, id: &U) -> bool {
+        self.pending.contains(id)
+    }
+
+    /// Number of commands still awaiting their responses.
+    #[inline]
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Match an incoming server message against the pending ids.
+    ///
+    /// A matched id is no longer pending afterwards.
+    pub fn match_response<T, E>(
+        &mut self,
+        message: ServerMessage<T, U, E>,
+    ) -> Correlation<T, U, E> {
+        let response = match message {
+            ServerMessage::Response(response) => response,
+            ServerMessage::Event(event) => return Correlation::Event(event),
+        };
+        let position = response
+            .id
+            .as_ref()
+            .and_then(|id| self.pending.iter().position(|pending| pending == id));
+        match position {
+            Some(index) => {
+                self.pending.swap_remove(index);
+                Correlation::Matched(response)
+            }
+            None => Correlation::Unmatched(response),
+        }
+    }
+}
+
+impl<U: PartialEq> Default for IdCorrelator<U> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Error of a QMP client.
+#[derive(Debug)]
+pub enum ClientError {
+    /// Reading from or writing to the underlying stream failed.
+    Io(io::Error),
+    /// A message could not be decoded, or the server failed to execute a command.
+    Qmp(QmpError),
+    /// The server closed the connection.
+    Disconnected,
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Io(err) => write!(f, "i/o error: {err}"),
+            ClientError::Qmp(err) => err.fmt(f),
+            ClientError::Disconnected => f.write_str("server closed the connection"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClientError::Io(err) => Some(err),
+            ClientError::Qmp(err) => Some(err),
+            ClientError::Disconnected => None,
+        }
+    }
+}
+
+impl From<io::Error> for ClientError {
+    #[inline]
+    fn from(err: io::Error) -> Self {
+        ClientError::Io(err)
+    }
+}
+
+impl From<QmpError> for ClientError {
+    #[inline]
+    fn from(err: QmpError) -> Self {
+        ClientError::Qmp(err)
+    }
+}
+
+impl From<serde_json::Error> for ClientError {
+    #[inline]
+    fn from(err: serde_json::Error) -> Self {
+        ClientError::Qmp(QmpError::Decode(err))
+    }
+}
+
+/// Error of [`QmpClient::graceful_shutdown`].
+#[derive(Debug)]
+pub enum GracefulError {
+    /// Communicating with the server failed.
+    Client(ClientError),
+    /// The guest did not shut down before the timeout.
+    TimedOut,
+    /// The emulator shut down, but not at the request of the guest, for
+    /// example after 'quit' or a signal.
+    NotGuestInitiated(ShutdownEvent),
+}
+
+impl fmt::Display for GracefulError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GracefulError::Client(err) => err.fmt(f),
+            GracefulError::TimedOut => f.write_str("guest did not shut down in time"),
+            GracefulError::NotGuestInitiated(_) => {
+                f.write_str("shutdown was not initiated by the guest")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GracefulError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GracefulError::Client(err) => Some(err),
+            GracefulError::TimedOut | GracefulError::NotGuestInitiated(_) => None,
+        }
+    }
+}
+
+impl From<ClientError> for GracefulError {
+    #[inline]
+    fn from(err: ClientError) -> Self {
+        GracefulError::Client(err)
+    }
+}
+
+/// A synchronous QMP client over a byte stream, such as a Unix or TCP socket.
+///
+/// Messages are exchanged as one json-object per line. Commands tracked by
+/// the client carry `u64` ids; their responses are kept until retrieved with
+/// [`QmpClient::take_response`].
+#[derive(Debug)]
+pub struct QmpClient<S> {
+    stream: BufReader<S>,
+    correlator: IdCorrelator<u64>,
+    responses: Vec<Response<Value, u64>>,
+    ids: IdGenerator,
+    version: Option<VersionInfo>,
+    partial_line: String,
+    handshake_attempts: u32,
+    retry_delay: Duration,
+}
+
+/// Builder of a [`QmpClient`] with a handshake retry policy.
+///
+/// By default the greeting is read once, as with [`QmpClient::new`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct QmpClientBuilder {
+    handshake_attempts: u32,
+    retry_delay: Duration,
+}
+
+impl QmpClientBuilder {
+    /// Create a builder with the default policy.
+    #[inline]
+    pub const fn new() -> Self {
+        QmpClientBuilder {
+            handshake_attempts: 1,
+            retry_delay: Duration::ZERO,
+        }
+    }
+
+    /// Attempt the handshake up to `n` times on transient read errors.
+    ///
+    /// `n` counts all the attempts, not only the extra ones: each timed-out
+    /// read of the greeting or of the `qmp_capabilities` reply uses up one
+    /// attempt, and the read is retried while attempts remain. The command
+    /// itself is sent once. Zero and one both mean a single attempt.
+    #[inline]
+    pub const fn handshake_retries(mut self, n: u32) -> Self {
+        self.handshake_attempts = if n == 0 { 1 } else { n };
+        self
+    }
+
+    /// Time to wait between two handshake read attempts.
+    #[inline]
+    pub const fn retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Create a client over a freshly connected stream with this policy.
+    ///
+    /// [`QmpClient::handshake`] should be called before issuing any command.
+    #[inline]
+    pub fn build<S: Read + Write>(self, stream: S) -> QmpClient<S> {
+        QmpClient {
+            stream: BufReader::new(stream),
+            correlator: IdCorrelator::new(),
+            responses: Vec::new(),
+            ids: IdGenerator::new(),
+            version: None,
+            partial_line: String::new(),
+            handshake_attempts: self.handshake_attempts,
+            retry_delay: self.retry_delay,
+        }
+    }
+}
+
+impl Default for QmpClientBuilder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Read + Write> QmpClient<S> {
+    /// Create a client over a freshly connected stream.
+    ///
+    /// [`QmpClient::handshake`] should be called before issuing any command.
+    #[inline]
+    pub fn new(stream: S) -> Self {
+        QmpClientBuilder::new().build(stream)
+    }
+
+    /// Read the server greeting and negotiate capabilities.
+    ///
+    /// No capability is enabled; the client enters command mode on success.
+    /// Reading the greeting and the reply to `qmp_capabilities` is retried
+    /// on transient read errors as configured with
+    /// [`QmpClientBuilder::handshake_retries`].
+    pub fn handshake(&mut self) -> Result<ServerGreeting, ClientError> {
+        let mut attempt = 1;
+        let line = self.read_handshake_line(&mut attempt)?;
+        let greeting: ServerGreeting = serde_json::from_str(&line)?;
+        self.send(&qmp_capabilities(None))?;
+        loop {
+            let line = self.read_handshake_line(&mut attempt)?;
+            match serde_json::from_str(&line)? {
+                ServerMessage::Response(Response { response, id: None }) => {
+                    return match response {
+                        ReturnOrError::Return { .. } => Ok(greeting),
+                        ReturnOrError::Error { error } => Err(QmpError::Server(error).into()),
+                    };
+                }
+                message => self.dispatch(message),
+            }
+        }
+    }
+
+    /// Send a command without tracking its response.
+    pub fn send<T: Serialize, U: Serialize>(
+        &mut self,
+        command: &Command<T, U>,
+    ) -> Result<(), ClientError> {
+        let mut line = serde_json::to_vec(command)?;
+        line.push(b'\n');
+        let stream = self.stream.get_mut();
+        stream.write_all(&line)?;
+        stream.flush()?;
+        Ok(())
+    }
+
+    /// Send a command, tracking its response by id if it has one.
+    pub fn send_tracked<T: Serialize>(
+        &mut self,
+        command: &SimpleCommand<T>,
+    ) -> Result<(), ClientError> {
+        self.send(command)?;
+        if let Some(id) = command.id {
+            self.correlator.register(id);
+        }
+        Ok(())
+    }
+
+    /// Take the response of a tracked command, if it was received.
+    pub fn take_response(&mut self, id: u64) -> Option<Response<Value, u64>> {
+        let index = self
+            .responses
+            .iter()
+            .position(|response| response.id == Some(id))?;
+        Some(self.responses.swap_remove(index))
+    }
+
+    /// Read the next message from the server.
+    pub fn read_message(&mut self) -> Result<ServerMessage<Value, u64, Value>, ClientError> {
+        let line = self.read_line()?;
+        Ok(serde_json::from_str(&line)?)
+    }
+
+    /// Read messages until an event satisfies the predicate, and return that event.
+    ///
+    /// Responses read meanwhile are dispatched to their tracked ids, and
+    /// events not satisfying the predicate are dropped. A known event whose
+    /// data does not match its structure is passed as `AnyEvent::Other`.
+    pub fn run_until<F>(&mut self, mut predicate: F) -> Result<AnyEvent, ClientError>
+    where
+        F: FnMut(&AnyEvent) -> bool,
+    {
+        loop {
+            let message = self.read_message()?;
+            if let ServerMessage::Event(event) = message {
+                let event = decode_event(event);
+                if predicate(&event) {
+                    return Ok(event);
+                }
+            } else {
+                self.dispatch(message);
+            }
+        }
+    }
+
+    /// Reset the guest with 'system_reset', and wait until it is actually reset.
+    ///
+    /// Returns the data of the RESET event, or the server error if the
+    /// command failed. Other events read meanwhile are dropped.
+    pub fn reset_and_wait(&mut self) -> Result<ResetEvent, ClientError> {
+        self.send_and_wait(&system_reset(), |event| match event {
+            AnyEvent::Reset { data, .. } => Some(data),
+            _ => None,
+        })
+    }
+
+    /// Resume the guest with 'cont', and wait until it is actually running.
+    ///
+    /// Returns the time of the RESUME event, or the server error if the
+    /// command failed. Other events read meanwhile are dropped.
+    pub fn cont_and_wait_resume(&mut self) -> Result<Timestamp, ClientError> {
+        self.send_and_wait(&cont(), |event| match event {
+            AnyEvent::Resume { timestamp } => Some(timestamp),
+            _ => None,
+        })
+    }
+
+    /// Stop the guest with 'stop', and wait until it is actually stopped.
+    ///
+    /// Returns the time of the STOP event, or the server error if the
+    /// command failed. Other events read meanwhile are dropped.
+    pub fn stop_and_wait(&mut self) -> Result<Timestamp, ClientError> {
+        self.send_and_wait(&stop(), |event| match event {
+            AnyEvent::Stop { timestamp } => Some(timestamp),
+            _ => None,
+        })
+    }
+
+    /// Ask the guest to power down with 'system_powerdown', and wait for it
+    /// to shut down.
+    ///
+    /// Returns the data of the SHUTDOWN event, or `GracefulError::TimedOut`
+    /// if it did not arrive within `timeout`; the caller may then terminate
+    /// the emulator with 'quit'. A SHUTDOWN whose reason is not initiated by
+    /// the guest is reported as `GracefulError::NotGuestInitiated`. Other
+    /// events read meanwhile are dropped.
+    ///
+    /// The timeout is only checked between reads, so the caller must set a
+    /// read timeout on the stream, for example with
+    /// `UnixStream::set_read_timeout`; without one, a silent server blocks
+    /// this call past the deadline. Reads timing out are retried after
+    /// sleeping for a short poll interval.
+    pub fn graceful_shutdown(&mut self, timeout: Duration) -> Result<ShutdownEvent, GracefulError> {
+        self.graceful_shutdown_with_clock(timeout, Instant::now, std::thread::sleep)
+    }
+
+    fn graceful_shutdown_with_clock(
+        &mut self,
+        timeout: Duration,
+        mut now: impl FnMut() -> Instant,
+        mut sleep: impl FnMut(Duration),
+    ) -> Result<ShutdownEvent, GracefulError> {
+        self.send(&system_powerdown())?;
+        let deadline = now() + timeout;
+        while now() < deadline {
+            let message = match self.read_message() {
+                Err(err) if is_transient(&err) => {
+                    sleep(POLL_INTERVAL);
+                    continue;
+                }
+                message => message?,
+            };
+            match message {
+                ServerMessage::Response(Response {
+                    response: ReturnOrError::Error { error },
+                    id: None,
+                }) => return Err(ClientError::from(QmpError::Server(error)).into()),
+                ServerMessage::Event(event) => {
+                    if let AnyEvent::Shutdown { data, .. } = decode_event(event) {
+                        if !data.reason.is_guest_initiated() {
+                            return Err(GracefulError::NotGuestInitiated(data));
+                        }
+                        return Ok(data);
+                    }
+                }
+                message => self.dispatch(message),
+            }
+        }
+        Err(GracefulError::TimedOut)
+    }
+
+    /// Version of the server, queried with 'query-version' on first use.
+    ///
+    /// The version does not change during a session, so later calls return
+    /// the stored value without querying the server again.
+    pub fn cached_version(&mut self) -> Result<&VersionInfo, ClientError> {
+        let version = match self.version.take() {
+            Some(version) => version,
+            None => serde_json::from_value(self.execute_auto_id(query_version())?)?,
+        };
+        Ok(self.version.insert(version))
+    }
+
+    /// Send a command with a fresh id, and wait for the data it returns.
+    ///
+    /// Responses to other tracked commands read meanwhile are kept for
+    /// [`QmpClient::take_response`], and events are dropped.
+    pub fn execute_auto_id<T: Serialize>(
+        &mut self,
+        command: Command<T, ()>,
+    ) -> Result<Value, ClientError> {
+        let id = self.ids.next().expect("id generator is endless");
+        self.send_tracked(&command.with_id(id))?;
+        loop {
+            if let Some(response) = self.take_response(id) {
+                return match response.response {
+                    ReturnOrError::Return { value } => Ok(value),
+                    ReturnOrError::Error { error } => Err(QmpError::Server(error).into()),
+                };
+            }
+            let message = self.read_message()?;
+            self.dispatch(message);
+        }
+    }
+
+    // Keep responses of tracked commands; drop unknown responses and events.
+    fn dispatch(&mut self, message: ServerMessage<Value, u64, Value>) {
+        if let Correlation::Matched(response) = self.correlator.match_response(message) {
+            self.responses.push(response);
+        }
+    }
+
+    // Send an untracked command, then wait for both its return and the first
+    // matching event, in either order, so that no stale return is left over
+    // for the next command.
+    fn send_and_wait<R>(
+        &mut self,
+        command: &Command<(), ()>,
+        mut matches: impl FnMut(AnyEvent) -> Option<R>,
+    ) -> Result<R, ClientError> {
+        self.send(command)?;
+        let mut returned = false;
+        let mut result = None;
+        loop {
+            match self.read_message()? {
+                ServerMessage::Response(Response {
+                    response: ReturnOrError::Error { error },
+                    id: None,
+                }) => return Err(QmpError::Server(error).into()),
+                ServerMessage::Response(Response {
+                    response: ReturnOrError::Return { .. },
+                    id: None,
+                }) => returned = true,
+                ServerMessage::Event(event) => {
+                    if result.is_none() {
+                        result = matches(decode_event(event));
+                    }
+                }
+                message => self.dispatch(message),
+            }
+            if returned && let Some(result) = result.take() {
+                return Ok(result);
+            }
+        }
+    }
+
+    // Bytes read before an interrupted read are kept in `partial_line`, so
+    // that a stream with a read timeout does not lose half a message.
+    // Read a line during the handshake, retrying on transient read errors
+    // while fewer than `handshake_attempts` attempts were made.
+    fn read_handshake_line(&mut self, attempt: &mut u32) -> Result<String, ClientError> {
+        loop {
+            match self.read_line() {
+                Err(err) if is_transient(&err) && *attempt < self.handshake_attempts => {
+                    *attempt += 1;
+                    std::thread::sleep(self.retry_delay);
+                }
+                line => return line,
+            }
+        }
+    }
+
+    fn read_line(&mut self) -> Result<String, ClientError> {
+        loop {
+            if self.stream.read_line(&mut self.partial_line)? == 0 {
+                return Err(ClientError::Disconnected);
+            }
+            let line = mem::take(&mut self.partial_line);
+            if !line.trim().is_empty() {
+                return Ok(line);
+            }
+        }
+    }
+}
+
+// Time to wait after a read timed out before polling the stream again,
+// so that a stream with a short read timeout does not spin.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+// Decode an event, keeping a known event with unexpected data as
+// `AnyEvent::Other`, so that a single malformed event does not end a wait.
+fn decode_event(event: Event<Value>) -> AnyEvent {
+    AnyEvent::from_event(event.clone()).unwrap_or(AnyEvent::Other {
+        event: event.event,
+        data: event.data,
+        timestamp: event.timestamp,
+    })
+}
+
+// Read errors of a stream with a read timeout, after which reading again
+// may succeed.
+fn is_transient(err: &ClientError) -> bool {
+    matches!(err, ClientError::Io(err) if matches!(
+        err.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ClientError, Correlation, GracefulError, IdCorrelator, POLL_INTERVAL, QmpClient,
+        QmpClientBuilder, ServerMessage,
+    };
+    use crate::events::AnyEvent;
+    use crate::generic::QmpError;
+    use crate::vm_run_state::{ResetEvent, ShutdownCause, ShutdownEvent};
+    use alloc::{format, string::String, vec::Vec};
+    use serde_json::Value;
+    use std::io::{self, Cursor, Read, Write};
+    use std::time::{Duration, Instant};
+
+    const GREETING: &str = r#"{"QMP": {"version": {"qemu": {"micro": 0, "minor": 2, "major": 8}, "package": ""}, "capabilities": ["oob"]}}"#;
+
+    // A server replaying canned output, and recording what the client writes.
+    struct FakeServer {
+        input: Cursor<Vec<u8>>,
+        output: Vec<u8>,
+        stall: bool,
+        failing_reads: usize,
+        failing_read_at: Option<u64>,
+    }
+
+    impl FakeServer {
+        fn new(lines: &[&str]) -> Self {
+            let mut input = String::new();
+            for line in lines {
+                input.push_str(line);
+                input.push_str("\r\n");
+            }
+            FakeServer {
+                input: Cursor::new(input.into_bytes()),
+                output: Vec::new(),
+                stall: false,
+                failing_reads: 0,
+                failing_read_at: None,
+            }
+        }
+
+        // Time out reads once all the lines are consumed, instead of closing.
+        fn stalling(mut self) -> Self {
+            self.stall = true;
+            self
+        }
+
+        // Time out the first `n` reads, as a flaky socket would.
+        fn failing_reads(mut self, n: usize) -> Self {
+            self.failing_reads = n;
+            self
+        }
+
+        // Time out one read when reaching the line `index`.
+        fn failing_read_at_line(mut self, index: usize) -> Self {
+            let input = self.input.get_ref();
+            let offset = input
+                .windows(2)
+                .enumerate()
+                .filter(|(_, window)| *window == b"\r\n")
+                .nth(index - 1)
+                .map_or(0, |(offset, _)| offset + 2);
+            self.failing_read_at = Some(offset as u64);
+            self
+        }
+    }
+
+    impl Read for FakeServer {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.failing_reads > 0 {
+                self.failing_reads -= 1;
+                return Err(io::ErrorKind::TimedOut.into());
+            }
+            let mut buf = buf;
+            if let Some(offset) = self.failing_read_at {
+                let position = self.input.position();
+                if position == offset {
+                    self.failing_read_at = None;
+                    return Err(io::ErrorKind::TimedOut.into());
+                }
+                if position < offset {
+                    let len = buf.len().min((offset - position) as usize);
+                    buf = &mut buf[..len];
+                }
+            }
+            match self.input.read(buf)? {
+                0 if self.stall => Err(io::ErrorKind::WouldBlock.into()),
+                read => Ok(read),
+            }
+        }
+    }
+
+    impl Write for FakeServer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.output.write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn event(name: &str, data: &str) -> String {
+        format!(
+            r#"{{"event": "{name}", "data": {data}, "timestamp": {{"seconds": 1267041653, "microseconds": 9518}}}}"#
+        )
+    }
+
+    #[test]
+    fn correlate_out_of_order_responses() {
+        let mut correlator = IdCorrelator::new();
+        correlator.register(1);
+        correlator.register(2);
+        let mut feed = |input: &str| {
+            let message: ServerMessage<Value, u64, Value> = serde_json::from_str(input).unwrap();
+            correlator.match_response(message)
+        };
+        match feed(r#"{"return": {"status": "running"}, "id": 2}"#) {
+            Correlation::Matched(response) => assert_eq!(response.id, Some(2)),
+            other => panic!("unexpected correlation: {other:?}"),
+        }
+        match feed(
+            r#"{"event": "RESUME", "timestamp": {"seconds": 1267020223, "microseconds": 435656}}"#,
+        ) {
+            Correlation::Event(event) => assert_eq!(event.event, "RESUME"),
+            other => panic!("unexpected correlation: {other:?}"),
+        }
+        match feed(r#"{"return": {}, "id": 1}"#) {
+            Correlation::Matched(response) => assert_eq!(response.id, Some(1)),
+            other => panic!("unexpected correlation: {other:?}"),
+        }
+        match feed(r#"{"return": {}, "id": 1}"#) {
+            Correlation::Unmatched(response) => assert_eq!(response.id, Some(1)),
+            other => panic!("unexpected correlation: {other:?}"),
+        }
+        assert_eq!(correlator.pending_count(), 0);
+    }
+
+    #[test]
+    fn run_until_shutdown() {
+        let reset = event("RESET", r#"{"guest": true, "reason": "guest-reset"}"#);
+        let shutdown = event("SHUTDOWN", r#"{"guest": true, "reason": "guest-shutdown"}"#);
+        let server = FakeServer::new(&[GREETING, r#"{"return": {}}"#, &reset, &shutdown]);
+        let mut client = QmpClient::new(server);
+        let greeting = client.handshake().unwrap();
+        assert_eq!(greeting.qmp.version.qemu.major, 8);
+        let mut seen = Vec::new();
+        let event = client
+            .run_until(|event| {
+                seen.push(String::from(event.name()));
+                matches!(event, AnyEvent::Shutdown { .. })
+            })
+            .unwrap();
+        assert_eq!(event.name(), "SHUTDOWN");
+        assert_eq!(seen, ["RESET", "SHUTDOWN"]);
+        assert!(matches!(
+            client.run_until(|_| true),
+            Err(ClientError::Disconnected)
+        ));
+        let output = String::from_utf8(client.stream.into_inner().output).unwrap();
+        assert_eq!(
+            output,
+            "{\"execute\":\"qmp_capabilities\",\"arguments\":{}}\n"
+        );
+    }
+
+    #[test]
+    fn reset_and_wait() {
+        let reset = event(
+            "RESET",
+            r#"{"guest": false, "reason": "host-qmp-system-reset"}"#,
+        );
+        let server = FakeServer::new(&[GREETING, r#"{"return": {}}"#, r#"{"return": {}}"#, &reset]);
+        let mut client = QmpClient::new(server);
+        client.handshake().unwrap();
+        let data = client.reset_and_wait().unwrap();
+        assert_eq!(
+            data,
+            ResetEvent {
+                guest: false,
+                reason: ShutdownCause::HostQmpSystemReset,
+            }
+        );
+        let output = String::from_utf8(client.stream.into_inner().output).unwrap();
+        assert!(output.ends_with("{\"execute\":\"system_reset\"}\n"));
+    }
+
+    #[test]
+    fn cached_version_queries_once() {
+        let version = r#"{"return": {"qemu": {"micro": 0, "minor": 2, "major": 8}, "package": "v8.2.0"}, "id": 0}"#;
+        let server = FakeServer::new(&[GREETING, r#"{"return": {}}"#, version]);
+        let mut client = QmpClient::new(server);
+        client.handshake().unwrap();
+        assert_eq!(client.cached_version().unwrap().qemu.minor, 2);
+        assert_eq!(client.cached_version().unwrap().package, "v8.2.0");
+        let output = String::from_utf8(client.stream.into_inner().output).unwrap();
+        assert_eq!(output.matches("query-version").count(), 1);
+        assert!(output.ends_with("{\"execute\":\"query-version\",\"id\":0}\n"));
+    }
+
+    #[test]
+    fn cont_and_wait_resume() {
+        let resume = event("RESUME", "{}");
+        let server =
+            FakeServer::new(&[GREETING, r#"{"return": {}}"#, r#"{"return": {}}"#, &resume]);
+        let mut client = QmpClient::new(server);
+        client.handshake().unwrap();
+        let timestamp = client.cont_and_wait_resume().unwrap();
+        assert_eq!(timestamp.seconds, 1267041653);
+        let output = String::from_utf8(client.stream.into_inner().output).unwrap();
+        assert!(output.ends_with("{\"execute\":\"cont\"}\n"));
+    }
+
+    #[test]
+    fn stop_and_wait() {
+        let resume = event("RESUME", "{}");
+        let stop = event("STOP", "{}");
+        let server = FakeServer::new(&[
+            GREETING,
+            r#"{"return": {}}"#,
+            &resume,
+            r#"{"return": {}}"#,
+            &stop,
+        ]);
+        let mut client = QmpClient::new(server);
+        client.handshake().unwrap();
+        let timestamp = client.stop_and_wait().unwrap();
+        assert_eq!(timestamp.microseconds, 9518);
+        let output = String::from_utf8(client.stream.into_inner().output).unwrap();
+        assert!(output.ends_with("{\"execute\":\"stop\"}\n"));
+    }
+
+    #[test]
+    fn stop_and_wait_error() {
+        let error = r#"{"error": {"class": "GenericError", "desc": "boom"}}"#;
+        let server = FakeServer::new(&[GREETING, r#"{"return": {}}"#, error]);
+        let mut client = QmpClient::new(server);
+        client.handshake().unwrap();
+        assert!(matches!(
+            client.stop_and_wait(),
+            Err(ClientError::Qmp(QmpError::Server(_)))
+        ));
+    }
+
+    #[test]
+    fn graceful_shutdown() {
+        let powerdown = event("POWERDOWN", "{}");
+        let shutdown = event("SHUTDOWN", r#"{"guest": true, "reason": "guest-shutdown"}"#);
+        let server = FakeServer::new(&[
+            GREETING,
+            r#"{"return": {}}"#,
+            r#"{"return": {}}"#,
+            &powerdown,
+            &shutdown,
+        ])
+        .stalling();
+        let mut client = QmpClient::new(server);
+        client.handshake().unwrap();
+        let start = Instant::now();
+        let data = client
+            .graceful_shutdown_with_clock(Duration::from_secs(30), || start, |_| {})
+            .unwrap();
+        assert_eq!(
+            data,
+            ShutdownEvent {
+                guest: true,
+                reason: ShutdownCause::GuestShutdown,
+            }
+        );
+        let output = String::from_utf8(client.stream.into_inner().output).unwrap();
+        assert!(output.ends_with("{\"execute\":\"system_powerdown\"}\n"));
+    }
+
+    #[test]
+    fn graceful_shutdown_timed_out() {
+        let powerdown = event("POWERDOWN", "{}");
+        let server = FakeServer::new(&[
+            GREETING,
+            r#"{"return": {}}"#,
+            r#"{"return": {}}"#,
+            &powerdown,
+        ])
+        .stalling();
+        let mut client = QmpClient::new(server);
+        client.handshake().unwrap();
+        let start = Instant::now();
+        let mut ticks = 0;
+        let mut sleeps = Vec::new();
+        let clock = || {
+            ticks += 1;
+            start + Duration::from_secs(ticks)
+        };
+        assert!(matches!(
+            client.graceful_shutdown_with_clock(Duration::from_secs(5), clock, |interval| {
+                sleeps.push(interval)
+            }),
+            Err(GracefulError::TimedOut)
+        ));
+        assert_eq!(ticks, 6);
+        assert_eq!(sleeps, [POLL_INTERVAL; 2]);
+    }
+
+    #[test]
+    fn handshake_retries_transient_greeting_read() {
+        let server = FakeServer::new(&[GREETING, r#"{"return": {}}"#]).failing_reads(1);
+        let mut client = QmpClientBuilder::new()
+            .handshake_retries(3)
+            .retry_delay(Duration::ZERO)
+            .build(server);
+        let greeting = client.handshake().unwrap();
+        assert_eq!(greeting.qmp.version.qemu.minor, 2);
+        let output = String::from_utf8(client.stream.into_inner().output).unwrap();
+        assert_eq!(
+            output,
+            "{\"execute\":\"qmp_capabilities\",\"arguments\":{}}\n"
+        );
+    }
+
+    #[test]
+    fn handshake_retries_transient_capabilities_read() {
+        let server = FakeServer::new(&[GREETING, r#"{"return": {}}"#]).failing_read_at_line(1);
+        let mut client = QmpClientBuilder::new().handshake_retries(2).build(server);
+        client.handshake().unwrap();
+        let output = String::from_utf8(client.stream.into_inner().output).unwrap();
+        assert_eq!(
+            output,
+            "{\"execute\":\"qmp_capabilities\",\"arguments\":{}}\n"
+        );
+        let server = FakeServer::new(&[GREETING, r#"{"return": {}}"#]).failing_read_at_line(1);
+        let mut client = QmpClient::new(server);
+        match client.handshake() {
+            Err(ClientError::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::TimedOut),
+            other => panic!("unexpected handshake result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn handshake_gives_up_after_retries() {
+        let server = FakeServer::new(&[GREETING, r#"{"return": {}}"#]).failing_reads(1);
+        let mut client = QmpClient::new(server);
+        match client.handshake() {
+            Err(ClientError::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::TimedOut),
+            other => panic!("unexpected handshake result: {other:?}"),
+        }
+        let server = FakeServer::new(&[GREETING, r#"{"return": {}}"#]).failing_reads(2);
+        let mut client = QmpClientBuilder::new().handshake_retries(2).build(server);
+        assert!(matches!(client.handshake(), Err(ClientError::Io(_))));
+        assert!(client.stream.into_inner().output.is_empty());
+    }
+
+    #[test]
+    fn run_until_skips_malformed_event() {
+        let malformed = event("RESET", r#"{"guest": "yes"}"#);
+        let shutdown = event("SHUTDOWN", r#"{"guest": true, "reason": "guest-shutdown"}"#);
+        let server = FakeServer::new(&[GREETING, r#"{"return": {}}"#, &malformed, &shutdown]);
+        let mut client = QmpClient::new(server);
+        client.handshake().unwrap();
+        let mut seen = Vec::new();
+        let event = client
+            .run_until(|event| {
+                seen.push(matches!(event, AnyEvent::Other { .. }));
+                matches!(event, AnyEvent::Shutdown { .. })
+            })
+            .unwrap();
+        assert_eq!(event.name(), "SHUTDOWN");
+        assert_eq!(seen, [true, false]);
+    }
+
+    #[test]
+    fn stop_and_wait_event_before_return() {
+        let malformed = event("RESET", r#"{"guest": "yes"}"#);
+        let stop = event("STOP", "{}");
+        let version =
+            r#"{"return": {"qemu": {"micro": 0, "minor": 2, "major": 8}, "package": ""}, "id": 0}"#;
+        let server = FakeServer::new(&[
+            GREETING,
+            r#"{"return": {}}"#,
+            &malformed,
+            &stop,
+            r#"{"return": {}}"#,
+            version,
+        ]);
+        let mut client = QmpClient::new(server);
+        client.handshake().unwrap();
+        let timestamp = client.stop_and_wait().unwrap();
+        assert_eq!(timestamp.seconds, 1267041653);
+        assert_eq!(client.cached_version().unwrap().qemu.major, 8);
+    }
+
+    #[test]
+    fn graceful_shutdown_host_initiated() {
+        let shutdown = event("SHUTDOWN", r#"{"guest": false, "reason": "host-signal"}"#);
+        let server = FakeServer::new(&[
+            GREETING,
+            r#"{"return": {}}"#,
+            r#"{"return": {}}"#,
+            &shutdown,
+        ])
+        .stalling();
+        let mut client = QmpClient::new(server);
+        client.handshake().unwrap();
+        let start = Instant::now();
+        match client.graceful_shutdown_with_clock(Duration::from_secs(30), || start, |_| {}) {
+            Err(GracefulError::NotGuestInitiated(data)) => {
+                assert_eq!(data.reason, ShutdownCause::HostSignal)
+            }
+            other => panic!("unexpected shutdown result: {other:?}"),
+        }
+    }
+}
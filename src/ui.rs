@@ -0,0 +1,621 @@
+//! Remote desktop and display.
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::generic::{Command, Empty, QmpCommand};
+use crate::sockets::NetworkAddressFamily;
+
+/// Specify the VNC reload options.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DisplayReloadOptionsVnc {
+    /// Reload tls certs or not.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_certs: Option<bool>,
+}
+
+/// Options of 'display-reload'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum DisplayReloadOptions {
+    /// VNC display.
+    Vnc(DisplayReloadOptionsVnc),
+}
+
+impl QmpCommand for DisplayReloadOptions {
+    type Return = Empty;
+    const NAME: &'static str = "display-reload";
+}
+
+/// Reload display configuration.
+///
+/// For now, only the TLS credentials of a VNC display can be reloaded,
+/// without restarting the display.
+#[inline]
+pub fn display_reload(params: DisplayReloadOptions) -> Command<DisplayReloadOptions, ()> {
+    Command {
+        execute: "display-reload".to_string(),
+        arguments: Some(params),
+        id: None,
+    }
+}
+
+/// Authentication scheme of a remote display server.
+///
+/// Schemes not known to this library are kept as `DisplayAuth::Unknown`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DisplayAuth {
+    /// No authentication.
+    None,
+    /// VNC password authentication.
+    Vnc,
+    /// RealVNC RA2 authentication.
+    Ra2,
+    /// RealVNC RA2 authentication without encryption.
+    Ra2ne,
+    /// Tight VNC authentication.
+    Tight,
+    /// Ultra VNC authentication.
+    Ultra,
+    /// TLS encryption with anonymous credentials.
+    Tls,
+    /// VeNCrypt authentication.
+    Vencrypt,
+    /// SASL authentication.
+    Sasl,
+    /// SPICE ticket (password) authentication.
+    Spice,
+    /// An authentication scheme not modeled by this library.
+    Unknown(String),
+}
+
+impl DisplayAuth {
+    /// Name of the authentication scheme, as sent in the `auth` member.
+    pub fn as_str(&self) -> &str {
+        match self {
+            DisplayAuth::None => "none",
+            DisplayAuth::Vnc => "vnc",
+            DisplayAuth::Ra2 => "ra2",
+            DisplayAuth::Ra2ne => "ra2ne",
+            DisplayAuth::Tight => "tight",
+            DisplayAuth::Ultra => "ultra",
+            DisplayAuth::Tls => "tls",
+            DisplayAuth::Vencrypt => "vencrypt",
+            DisplayAuth::Sasl => "sasl",
+            DisplayAuth::Spice => "spice",
+            DisplayAuth::Unknown(name) => name,
+        }
+    }
+}
+
+impl From<&str> for DisplayAuth {
+    fn from(name: &str) -> Self {
+        match name {
+            "none" => DisplayAuth::None,
+            "vnc" => DisplayAuth::Vnc,
+            "ra2" => DisplayAuth::Ra2,
+            "ra2ne" => DisplayAuth::Ra2ne,
+            "tight" => DisplayAuth::Tight,
+            "ultra" => DisplayAuth::Ultra,
+            "tls" => DisplayAuth::Tls,
+            "vencrypt" => DisplayAuth::Vencrypt,
+            "sasl" => DisplayAuth::Sasl,
+            "spice" => DisplayAuth::Spice,
+            _ => DisplayAuth::Unknown(name.to_string()),
+        }
+    }
+}
+
+impl Serialize for DisplayAuth {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for DisplayAuth {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(DisplayAuth::from(name.as_str()))
+    }
+}
+
+/// Information about a connected VNC client.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct VncClientInfo {
+    /// IP address.
+    pub host: String,
+    /// The service name of the VNC port.
+    pub service: String,
+    /// Address family.
+    pub family: NetworkAddressFamily,
+    /// True in case the socket is a websocket.
+    pub websocket: bool,
+    /// If x509 authentication is in use, the Distinguished Name of the client.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x509_dname: Option<String>,
+    /// If SASL authentication is in use, the SASL username used for authentication.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sasl_username: Option<String>,
+}
+
+/// Information about the VNC session.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct VncInfo {
+    /// True if the VNC server is enabled, false otherwise.
+    pub enabled: bool,
+    /// The hostname the VNC server is bound to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// The address family the VNC server is bound to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family: Option<NetworkAddressFamily>,
+    /// The service name of the VNC server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+    /// The current authentication type used by the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<DisplayAuth>,
+    /// A list of `VncClientInfo` of all currently connected clients.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clients: Option<Vec<VncClientInfo>>,
+}
+
+/// Marker of 'query-vnc'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryVnc;
+
+impl QmpCommand for QueryVnc {
+    type Return = VncInfo;
+    const NAME: &'static str = "query-vnc";
+}
+
+/// Returns information about the current VNC server.
+#[inline]
+pub fn query_vnc() -> Command<(), ()> {
+    Command {
+        execute: "query-vnc".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// The mouse mode of a SPICE session.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpiceQueryMouseMode {
+    /// Mouse cursor position is determined by the client.
+    Client,
+    /// Mouse cursor position is determined by the server.
+    Server,
+    /// No information is available about mouse mode used by the spice server.
+    Unknown,
+}
+
+/// Information about a SPICE client channel.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct SpiceChannel {
+    /// IP address.
+    pub host: String,
+    /// Port number.
+    pub port: String,
+    /// Address family.
+    pub family: NetworkAddressFamily,
+    /// SPICE connection id number.
+    ///
+    /// All channels with the same id belong to the same SPICE session.
+    pub connection_id: i64,
+    /// SPICE channel type number. "1" is the main control channel, filter
+    /// for this one if you want to track spice sessions only.
+    pub channel_type: i64,
+    /// SPICE channel ID number. Usually "0", might be different when
+    /// multiple channels of the same type exist, such as multiple display
+    /// channels in a multihead setup.
+    pub channel_id: i64,
+    /// True if the channel is encrypted, false otherwise.
+    pub tls: bool,
+}
+
+/// Information about the SPICE session.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct SpiceInfo {
+    /// True if the SPICE server is enabled, false otherwise.
+    pub enabled: bool,
+    /// True if the last guest migration completed and spice migration had
+    /// completed as well, false otherwise.
+    pub migrated: bool,
+    /// The hostname the SPICE server is bound to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// The SPICE server's port number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<i64>,
+    /// The SPICE server's TLS port number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_port: Option<i64>,
+    /// The current authentication type used by the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<DisplayAuth>,
+    /// SPICE server version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compiled_version: Option<String>,
+    /// The mode in which the mouse cursor is displayed currently.
+    pub mouse_mode: SpiceQueryMouseMode,
+    /// A list of `SpiceChannel` for each active spice channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channels: Option<Vec<SpiceChannel>>,
+}
+
+impl SpiceInfo {
+    /// Number of active SPICE channels.
+    #[inline]
+    pub fn channel_count(&self) -> usize {
+        self.channels.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Whether any active channel has the given type.
+    ///
+    /// `ty` is either a SPICE channel name such as "main" or "display",
+    /// or the decimal channel type number as reported in 'channel-type'.
+    pub fn has_channel_type(&self, ty: &str) -> bool {
+        let Some(number) = spice_channel_type(ty) else {
+            return false;
+        };
+        self.channels
+            .iter()
+            .flatten()
+            .any(|channel| channel.channel_type == number)
+    }
+}
+
+fn spice_channel_type(ty: &str) -> Option<i64> {
+    let number = match ty {
+        "main" => 1,
+        "display" => 2,
+        "inputs" => 3,
+        "cursor" => 4,
+        "playback" => 5,
+        "record" => 6,
+        "tunnel" => 7,
+        "smartcard" => 8,
+        "usbredir" => 9,
+        "port" => 10,
+        "webdav" => 11,
+        _ => return ty.parse().ok(),
+    };
+    Some(number)
+}
+
+/// Marker of 'query-spice'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QuerySpice;
+
+impl QmpCommand for QuerySpice {
+    type Return = SpiceInfo;
+    const NAME: &'static str = "query-spice";
+}
+
+/// Returns information about the current SPICE server.
+#[inline]
+pub fn query_spice() -> Command<(), ()> {
+    Command {
+        execute: "query-spice".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+/// The basic information for SPICE network connection.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct SpiceBasicInfo {
+    /// IP address.
+    pub host: String,
+    /// Port number.
+    pub port: String,
+    /// Address family.
+    pub family: NetworkAddressFamily,
+}
+
+/// Information about a SPICE server.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct SpiceServerInfo {
+    /// IP address.
+    pub host: String,
+    /// Port number.
+    pub port: String,
+    /// Address family.
+    pub family: NetworkAddressFamily,
+    /// Authentication method.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<DisplayAuth>,
+}
+
+/// Information about a disconnected SPICE client.
+///
+/// QEMU reports the basic network information only; the channel details
+/// of `SpiceChannel` are accepted when present.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct SpiceClientInfo {
+    /// IP address.
+    pub host: String,
+    /// Port number.
+    pub port: String,
+    /// Address family.
+    pub family: NetworkAddressFamily,
+    /// SPICE connection id number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection_id: Option<i64>,
+    /// SPICE channel type number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_type: Option<i64>,
+    /// SPICE channel ID number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<i64>,
+    /// True if the channel is encrypted, false otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls: Option<bool>,
+}
+
+/// Emitted when a SPICE client establishes a connection.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct SpiceConnectedEvent {
+    /// Server information.
+    pub server: SpiceBasicInfo,
+    /// Client information.
+    pub client: SpiceBasicInfo,
+}
+
+/// Emitted after initial handshake and authentication takes place (if any)
+/// and the SPICE channel is up and running.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct SpiceInitializedEvent {
+    /// Server information.
+    pub server: SpiceServerInfo,
+    /// Client information.
+    pub client: SpiceChannel,
+}
+
+/// Emitted when the SPICE connection is closed.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct SpiceDisconnectedEvent {
+    /// Server information.
+    pub server: SpiceServerInfo,
+    /// Client information.
+    pub client: SpiceClientInfo,
+}
+
+impl SpiceDisconnectedEvent {
+    /// SPICE connection id of the disconnected client, if reported.
+    ///
+    /// All channels with the same id belong to the same SPICE session.
+    #[inline]
+    pub fn connection_id(&self) -> Option<i64> {
+        self.client.connection_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DisplayAuth, DisplayReloadOptions, DisplayReloadOptionsVnc, SpiceChannel,
+        SpiceDisconnectedEvent, SpiceInfo, SpiceQueryMouseMode, VncClientInfo, VncInfo,
+        display_reload, query_spice, query_vnc,
+    };
+    use crate::sockets::NetworkAddressFamily;
+    use alloc::{string::ToString, vec};
+
+    #[test]
+    fn new_display_reload() {
+        let cmd = display_reload(DisplayReloadOptions::Vnc(DisplayReloadOptionsVnc {
+            tls_certs: Some(true),
+        }));
+        let compact_string =
+            r#"{"execute":"display-reload","arguments":{"type":"vnc","tls-certs":true}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_vnc_result() {
+        let cmd = query_vnc();
+        let compact_string = r#"{"execute":"query-vnc"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"{
+            "enabled": true,
+            "host": "0.0.0.0",
+            "service": "50402",
+            "auth": "sasl",
+            "family": "ipv4",
+            "clients": [
+                {
+                    "host": "127.0.0.1",
+                    "service": "50401",
+                    "family": "ipv4",
+                    "websocket": false
+                }
+            ]
+        }"#;
+        let value = VncInfo {
+            enabled: true,
+            host: Some("0.0.0.0".to_string()),
+            family: Some(NetworkAddressFamily::Ipv4),
+            service: Some("50402".to_string()),
+            auth: Some(DisplayAuth::Sasl),
+            clients: Some(vec![VncClientInfo {
+                host: "127.0.0.1".to_string(),
+                service: "50401".to_string(),
+                family: NetworkAddressFamily::Ipv4,
+                websocket: false,
+                x509_dname: None,
+                sasl_username: None,
+            }]),
+        };
+        assert_eq!(value, serde_json::from_str::<VncInfo>(string).unwrap());
+    }
+
+    #[test]
+    fn query_spice_result() {
+        let cmd = query_spice();
+        let compact_string = r#"{"execute":"query-spice"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"{
+            "enabled": true,
+            "auth": "x-future-auth",
+            "port": 5920,
+            "migrated": false,
+            "tls-port": 5921,
+            "host": "0.0.0.0",
+            "mouse-mode": "client",
+            "channels": [
+                {
+                    "port": "54924",
+                    "family": "ipv4",
+                    "channel-type": 1,
+                    "connection-id": 1804289383,
+                    "host": "127.0.0.1",
+                    "channel-id": 0,
+                    "tls": true
+                }
+            ]
+        }"#;
+        let value = SpiceInfo {
+            enabled: true,
+            migrated: false,
+            host: Some("0.0.0.0".to_string()),
+            port: Some(5920),
+            tls_port: Some(5921),
+            auth: Some(DisplayAuth::Unknown("x-future-auth".to_string())),
+            compiled_version: None,
+            mouse_mode: SpiceQueryMouseMode::Client,
+            channels: Some(vec![SpiceChannel {
+                host: "127.0.0.1".to_string(),
+                port: "54924".to_string(),
+                family: NetworkAddressFamily::Ipv4,
+                connection_id: 1804289383,
+                channel_type: 1,
+                channel_id: 0,
+                tls: true,
+            }]),
+        };
+        assert_eq!(value, serde_json::from_str::<SpiceInfo>(string).unwrap());
+        assert_eq!(
+            r#""x-future-auth""#,
+            serde_json::to_string(&value.auth).unwrap()
+        );
+    }
+
+    #[test]
+    fn vnc_websocket_and_tls_clients() {
+        let string = r#"{
+            "enabled": true,
+            "host": "::",
+            "service": "5900",
+            "auth": "vencrypt",
+            "family": "ipv6",
+            "clients": [
+                {
+                    "host": "192.168.0.10",
+                    "service": "41234",
+                    "family": "ipv4",
+                    "websocket": true
+                },
+                {
+                    "host": "fd00::10",
+                    "service": "41236",
+                    "family": "ipv6",
+                    "websocket": false,
+                    "x509_dname": "C=CN,O=Example,CN=client",
+                    "sasl_username": "alice"
+                }
+            ]
+        }"#;
+        let info: VncInfo = serde_json::from_str(string).unwrap();
+        assert_eq!(info.auth, Some(DisplayAuth::Vencrypt));
+        assert_eq!(info.family, Some(NetworkAddressFamily::Ipv6));
+        let clients = info.clients.unwrap();
+        assert!(clients[0].websocket);
+        assert_eq!(clients[0].x509_dname, None);
+        assert_eq!(
+            clients[1],
+            VncClientInfo {
+                host: "fd00::10".to_string(),
+                service: "41236".to_string(),
+                family: NetworkAddressFamily::Ipv6,
+                websocket: false,
+                x509_dname: Some("C=CN,O=Example,CN=client".to_string()),
+                sasl_username: Some("alice".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn spice_channel_types() {
+        let channel = |connection_id: i64, channel_type: i64, channel_id: i64| {
+            alloc::format!(
+                r#"{{"host": "127.0.0.1", "port": "54924", "family": "ipv4",
+                "connection-id": {connection_id}, "channel-type": {channel_type},
+                "channel-id": {channel_id}, "tls": false}}"#
+            )
+        };
+        let string = alloc::format!(
+            r#"{{"enabled": true, "migrated": false, "mouse-mode": "server",
+            "channels": [{}, {}, {}, {}]}}"#,
+            channel(1, 1, 0),
+            channel(1, 2, 0),
+            channel(1, 2, 1),
+            channel(1, 3, 0),
+        );
+        let info: SpiceInfo = serde_json::from_str(&string).unwrap();
+        assert_eq!(info.channel_count(), 4);
+        assert!(info.has_channel_type("main"));
+        assert!(info.has_channel_type("display"));
+        assert!(info.has_channel_type("3"));
+        assert!(!info.has_channel_type("usbredir"));
+        assert!(!info.has_channel_type("not-a-channel"));
+        let disabled: SpiceInfo = serde_json::from_str(
+            r#"{"enabled": false, "migrated": false, "mouse-mode": "unknown"}"#,
+        )
+        .unwrap();
+        assert_eq!(disabled.channel_count(), 0);
+        assert!(!disabled.has_channel_type("main"));
+    }
+
+    #[test]
+    fn spice_disconnected_event() {
+        let string = r#"{
+            "server": {"auth": "spice", "port": "5921", "family": "ipv4", "host": "127.0.0.1"},
+            "client": {"port": "49004", "family": "ipv4", "channel-type": 1,
+                "connection-id": 1804289383, "host": "127.0.0.1", "channel-id": 0, "tls": true}
+        }"#;
+        let event: SpiceDisconnectedEvent = serde_json::from_str(string).unwrap();
+        assert_eq!(event.server.auth, Some(DisplayAuth::Spice));
+        assert_eq!(event.server.family, NetworkAddressFamily::Ipv4);
+        assert_eq!(event.client.port, "49004");
+        assert_eq!(event.client.tls, Some(true));
+        assert_eq!(event.connection_id(), Some(1804289383));
+        let basic = r#"{
+            "server": {"port": "5920", "family": "ipv4", "host": "127.0.0.1"},
+            "client": {"port": "52873", "family": "ipv4", "host": "127.0.0.1"}
+        }"#;
+        let event: SpiceDisconnectedEvent = serde_json::from_str(basic).unwrap();
+        assert_eq!(event.connection_id(), None);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(basic).unwrap(),
+            serde_json::to_value(&event).unwrap()
+        );
+    }
+}
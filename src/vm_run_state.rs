@@ -1,3 +1,227 @@
 //! Virtual machine run states.
+use serde::{Deserialize, Serialize};
 
-// TODO structures.
+/// An enumeration of reasons for a shutdown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShutdownCause {
+    /// No shutdown request pending.
+    None,
+    /// An error prevents further use of guest.
+    HostError,
+    /// Reaction to the QMP command 'quit'.
+    HostQmpQuit,
+    /// Reaction to the QMP command 'system_reset'.
+    HostQmpSystemReset,
+    /// Reaction to a signal, such as SIGINT.
+    HostSignal,
+    /// Reaction to a UI event, like window close.
+    HostUi,
+    /// Guest shutdown/suspend request, via ACPI or other hardware-specific means.
+    GuestShutdown,
+    /// Guest reset request, and command line turns that into a shutdown.
+    GuestReset,
+    /// Guest panicked, and command line turns that into a shutdown.
+    GuestPanic,
+    /// Partial guest reset that does not trigger QMP events and ignores --no-reboot.
+    ///
+    /// This is useful for sanitizing hypercalls on s390 that are used during
+    /// kexec/kdump/boot.
+    SubsystemReset,
+    /// A snapshot is being loaded by the record & replay subsystem.
+    ///
+    /// This value is used only within QEMU. It doesn't occur in QMP.
+    SnapshotLoad,
+}
+
+impl ShutdownCause {
+    /// Whether the shutdown or reset was requested by the guest.
+    ///
+    /// True for 'guest-shutdown', 'guest-reset' and 'guest-panic'.
+    #[inline]
+    pub const fn is_guest_initiated(&self) -> bool {
+        matches!(
+            self,
+            ShutdownCause::GuestShutdown | ShutdownCause::GuestReset | ShutdownCause::GuestPanic
+        )
+    }
+
+    /// Whether the shutdown or reset was requested on the host side.
+    ///
+    /// True for all the 'host-*' causes. 'none', 'subsystem-reset' and
+    /// 'snapshot-load' are neither guest nor host initiated.
+    #[inline]
+    pub const fn is_host_initiated(&self) -> bool {
+        matches!(
+            self,
+            ShutdownCause::HostError
+                | ShutdownCause::HostQmpQuit
+                | ShutdownCause::HostQmpSystemReset
+                | ShutdownCause::HostSignal
+                | ShutdownCause::HostUi
+        )
+    }
+}
+
+/// Emitted when the virtual machine has shut down, indicating that qemu is about to exit.
+///
+/// If the command-line option "-no-shutdown" has been specified, qemu will not exit,
+/// and a STOP event will eventually follow the SHUTDOWN event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ShutdownEvent {
+    /// If true, the shutdown was triggered by a guest request
+    /// (such as a guest-initiated ACPI shutdown request or other
+    /// hardware-specific action) rather than a host request
+    /// (such as sending qemu a SIGINT).
+    pub guest: bool,
+    /// The `ShutdownCause` which resulted in the SHUTDOWN.
+    pub reason: ShutdownCause,
+}
+
+/// Emitted when the virtual machine is reset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ResetEvent {
+    /// If true, the reset was triggered by a guest request
+    /// (such as a guest-initiated ACPI reboot request or other
+    /// hardware-specific action) rather than a host request
+    /// (such as the QMP command 'system_reset').
+    pub guest: bool,
+    /// The `ShutdownCause` of the RESET.
+    pub reason: ShutdownCause,
+}
+
+/// An enumeration of the actions taken when guest OS panic is detected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GuestPanicAction {
+    /// System pauses.
+    Pause,
+    /// System powers off.
+    Poweroff,
+    /// System continues to run.
+    Run,
+}
+
+/// Reason why the guest crashed on s390.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum S390CrashReason {
+    /// No crash reason was set.
+    Unknown,
+    /// The CPU has entered a disabled wait state.
+    DisabledWait,
+    /// Clock comparator or cpu timer interrupt with new PSW enabled for external interrupts.
+    ExtintLoop,
+    /// Program interrupt with BAD new PSW.
+    PgmintLoop,
+    /// Operation exception interrupt with invalid code at the program interrupt new PSW.
+    OpintLoop,
+}
+
+/// Hyper-V specific guest panic information (HV crash MSRs).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct GuestPanicInformationHyperV {
+    /// Value of HV crash MSR P0.
+    pub arg1: u64,
+    /// Value of HV crash MSR P1.
+    pub arg2: u64,
+    /// Value of HV crash MSR P2.
+    pub arg3: u64,
+    /// Value of HV crash MSR P3.
+    pub arg4: u64,
+    /// Value of HV crash MSR P4.
+    pub arg5: u64,
+}
+
+/// S390 specific guest panic information (PSW).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "kebab-case")]
+pub struct GuestPanicInformationS390 {
+    /// Core id of the CPU that crashed.
+    pub core: u32,
+    /// Control fields of guest PSW.
+    pub psw_mask: u64,
+    /// Guest instruction address.
+    pub psw_addr: u64,
+    /// Guest crash reason.
+    pub reason: S390CrashReason,
+}
+
+/// Information about a guest panic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum GuestPanicInformation {
+    /// Hyper-V guest panic information.
+    HyperV(GuestPanicInformationHyperV),
+    /// s390 guest panic information.
+    S390(GuestPanicInformationS390),
+}
+
+/// Emitted when guest OS panic is detected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct GuestPanickedEvent {
+    /// Action that has been taken, currently always "pause".
+    pub action: GuestPanicAction,
+    /// Information about a panic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub info: Option<GuestPanicInformation>,
+}
+
+/// Emitted when guest OS crash loaded is detected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct GuestCrashloadedEvent {
+    /// Action that has been taken, currently always "run".
+    pub action: GuestPanicAction,
+    /// Information about a panic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub info: Option<GuestPanicInformation>,
+}
+
+// TODO other structures.
+
+#[cfg(test)]
+mod tests {
+    use super::{ResetEvent, ShutdownCause, ShutdownEvent};
+
+    #[test]
+    fn shutdown_cause_guest_initiated() {
+        let string = r#"{"guest": true, "reason": "guest-reset"}"#;
+        let event: ResetEvent = serde_json::from_str(string).unwrap();
+        assert!(event.reason.is_guest_initiated());
+        assert!(!event.reason.is_host_initiated());
+        let string = r#"{"guest": true, "reason": "guest-shutdown"}"#;
+        let event: ShutdownEvent = serde_json::from_str(string).unwrap();
+        assert!(event.reason.is_guest_initiated());
+        assert!(ShutdownCause::GuestPanic.is_guest_initiated());
+    }
+
+    #[test]
+    fn shutdown_cause_host_initiated() {
+        let string = r#"{"guest": false, "reason": "host-qmp-system-reset"}"#;
+        let event: ResetEvent = serde_json::from_str(string).unwrap();
+        assert!(event.reason.is_host_initiated());
+        assert!(!event.reason.is_guest_initiated());
+        for cause in [
+            ShutdownCause::HostError,
+            ShutdownCause::HostQmpQuit,
+            ShutdownCause::HostSignal,
+            ShutdownCause::HostUi,
+        ] {
+            assert!(cause.is_host_initiated());
+        }
+        for cause in [
+            ShutdownCause::None,
+            ShutdownCause::SubsystemReset,
+            ShutdownCause::SnapshotLoad,
+        ] {
+            assert!(!cause.is_host_initiated());
+            assert!(!cause.is_guest_initiated());
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Yank feature.
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::generic::{Command, Empty, OobSafe, QmpCommand};
+
+/// Specifies which block graph node to yank.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct YankInstanceBlockNode {
+    /// The name of the block node.
+    pub node_name: String,
+}
+
+/// Specifies which character device to yank.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct YankInstanceChardev {
+    /// The chardev's ID.
+    pub id: String,
+}
+
+/// A yank instance can be yanked with the 'yank' qmp command to recover
+/// from a hanging QEMU.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum YankInstance {
+    /// A block graph node.
+    BlockNode(YankInstanceBlockNode),
+    /// A character device.
+    Chardev(YankInstanceChardev),
+    /// The migration.
+    Migration,
+}
+
+/// Arguments of 'yank'.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct YankParams {
+    /// The instances to be yanked.
+    pub instances: Vec<YankInstance>,
+}
+
+impl QmpCommand for YankParams {
+    type Return = Empty;
+    const NAME: &'static str = "yank";
+}
+
+impl OobSafe for YankParams {}
+
+/// Try to recover from hanging QEMU by yanking the specified instances.
+///
+/// Yanking an instance means abruptly closing its network connections;
+/// see 'query-yank' for the instances that can be yanked. This command may
+/// be sent out-of-band.
+#[inline]
+pub fn yank(instances: Vec<YankInstance>) -> Command<YankParams, ()> {
+    Command {
+        execute: "yank".to_string(),
+        arguments: Some(YankParams { instances }),
+        id: None,
+    }
+}
+
+/// Marker of 'query-yank'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryYank;
+
+impl QmpCommand for QueryYank {
+    type Return = Vec<YankInstance>;
+    const NAME: &'static str = "query-yank";
+}
+
+/// Query yank instances.
+///
+/// The server returns a list of `YankInstance`.
+#[inline]
+pub fn query_yank() -> Command<(), ()> {
+    Command {
+        execute: "query-yank".to_string(),
+        arguments: None,
+        id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{YankInstance, YankInstanceBlockNode, YankInstanceChardev, query_yank, yank};
+    use alloc::{string::ToString, vec, vec::Vec};
+
+    #[test]
+    fn new_yank() {
+        let cmd = yank(vec![YankInstance::Chardev(YankInstanceChardev {
+            id: "chr0".to_string(),
+        })]);
+        let compact_string =
+            r#"{"execute":"yank","arguments":{"instances":[{"type":"chardev","id":"chr0"}]}}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+    }
+
+    #[test]
+    fn query_yank_result() {
+        let cmd = query_yank();
+        let compact_string = r#"{"execute":"query-yank"}"#;
+        assert_eq!(compact_string, serde_json::to_string(&cmd).unwrap());
+        let string = r#"[
+            {"type": "block-node", "node-name": "nbd0"},
+            {"type": "chardev", "id": "chr0"},
+            {"type": "migration"}
+        ]"#;
+        let value = vec![
+            YankInstance::BlockNode(YankInstanceBlockNode {
+                node_name: "nbd0".to_string(),
+            }),
+            YankInstance::Chardev(YankInstanceChardev {
+                id: "chr0".to_string(),
+            }),
+            YankInstance::Migration,
+        ];
+        assert_eq!(value, serde_json::from_str::<Vec<_>>(string).unwrap());
+    }
+}